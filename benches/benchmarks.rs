@@ -110,22 +110,24 @@ fn nested_function() {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
-        c.bench_function("basic", |b| b.iter(|| basic()));
-        c.bench_function("if_statement", |b| b.iter(|| if_statement()));
-        c.bench_function("senao", |b| b.iter(|| senao()));
-        c.bench_function("while_statement", |b| b.iter(|| while_statement()));
-        c.bench_function("math", |b| b.iter(|| math()));
-        c.bench_function("boolean", |b| b.iter(|| boolean()));
-        c.bench_function("function", |b| b.iter(|| function()));
-        c.bench_function("function_return", |b| b.iter(|| function_return()));
-        c.bench_function("fibonacci_iterative", |b| b.iter(|| fibonacci_iterative()));
-        c.bench_function("function_recursion", |b| b.iter(|| function_recursion()));
-        c.bench_function("hanoi_towers_recursion", |b| {
-            b.iter(|| hanoi_towers_recursion())
-        });
-        c.bench_function("ackermann", |b| b.iter(|| ackermann()));
-        c.bench_function("nested_function", |b| b.iter(|| nested_function()));
+    if Command::new("cargo")
+        .args(["build", "--release"])
+        .output()
+        .is_ok()
+    {
+        c.bench_function("basic", |b| b.iter(basic));
+        c.bench_function("if_statement", |b| b.iter(if_statement));
+        c.bench_function("senao", |b| b.iter(senao));
+        c.bench_function("while_statement", |b| b.iter(while_statement));
+        c.bench_function("math", |b| b.iter(math));
+        c.bench_function("boolean", |b| b.iter(boolean));
+        c.bench_function("function", |b| b.iter(function));
+        c.bench_function("function_return", |b| b.iter(function_return));
+        c.bench_function("fibonacci_iterative", |b| b.iter(fibonacci_iterative));
+        c.bench_function("function_recursion", |b| b.iter(function_recursion));
+        c.bench_function("hanoi_towers_recursion", |b| b.iter(hanoi_towers_recursion));
+        c.bench_function("ackermann", |b| b.iter(ackermann));
+        c.bench_function("nested_function", |b| b.iter(nested_function));
     } else {
         panic!("Cargo build failed");
     }
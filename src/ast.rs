@@ -16,8 +16,123 @@ pub enum Expression {
     BigrEq(Box<Expression>, Box<Expression>),
     And(Box<Expression>, Box<Expression>),
     Or(Box<Expression>, Box<Expression>),
-    FnCall(String, Vec<Expression>),
-    Entrada(InputType),
+    FnCall(String, Vec<Expression>, Vec<(String, Expression)>, usize),
+    Entrada(InputType, Option<Box<Expression>>),
+    Math(MathFn, Vec<Expression>),
+    List(Vec<Expression>),
+    RandomNumber(Box<Expression>, Box<Expression>),
+    RandomItem(Box<Expression>),
+    Convert(Box<Expression>, ConvertType),
+    TypeOf(Box<Expression>),
+    Dictionary(Vec<(Expression, Expression)>),
+    Lookup(String, Box<Expression>),
+    HasKey(String, Box<Expression>),
+    NewRecord(String, Vec<Expression>),
+    Field(String, Box<Expression>),
+    AnonymousFunction(Vec<String>, Vec<Box<AstNode>>),
+    ListOp(ListFn, Vec<Expression>),
+    Tuple(Vec<Expression>),
+}
+
+impl Expression {
+    pub fn variables(&self, names: &mut Vec<String>) {
+        match self {
+            Expression::Variable(ident) => {
+                if !names.contains(ident) {
+                    names.push(ident.clone())
+                }
+            }
+            Expression::Sum(left, right)
+            | Expression::Sub(left, right)
+            | Expression::Mult(left, right)
+            | Expression::Div(left, right)
+            | Expression::Is(left, right)
+            | Expression::IsNot(left, right)
+            | Expression::Smlr(left, right)
+            | Expression::Bigr(left, right)
+            | Expression::SmlrEq(left, right)
+            | Expression::BigrEq(left, right)
+            | Expression::And(left, right)
+            | Expression::Or(left, right)
+            | Expression::RandomNumber(left, right) => {
+                left.variables(names);
+                right.variables(names);
+            }
+            Expression::FnCall(_, args, named, _) => {
+                for arg in args {
+                    arg.variables(names);
+                }
+                for (_, arg) in named {
+                    arg.variables(names);
+                }
+            }
+            Expression::Math(_, args)
+            | Expression::List(args)
+            | Expression::Tuple(args)
+            | Expression::NewRecord(_, args)
+            | Expression::ListOp(_, args) => {
+                for arg in args {
+                    arg.variables(names);
+                }
+            }
+            Expression::Dictionary(entries) => {
+                for (key, value) in entries {
+                    key.variables(names);
+                    value.variables(names);
+                }
+            }
+            Expression::Lookup(ident, key) | Expression::HasKey(ident, key) => {
+                Expression::Variable(ident.clone()).variables(names);
+                key.variables(names);
+            }
+            Expression::RandomItem(expr)
+            | Expression::Convert(expr, _)
+            | Expression::TypeOf(expr)
+            | Expression::Field(_, expr) => expr.variables(names),
+            Expression::Entrada(_, prompt) => {
+                if let Some(prompt) = prompt {
+                    prompt.variables(names)
+                }
+            }
+            Expression::Value(_) | Expression::AnonymousFunction(_, _) => {}
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MathFn {
+    SquareRoot,
+    Power,
+    Round,
+    Floor,
+    Ceil,
+    Abs,
+    Sin,
+    Cos,
+    Pi,
+    Min,
+    Max,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ListFn {
+    Map,
+    Filter,
+    Reduce,
+    Sort,
+    Any,
+    All,
+    Sum,
+    Mean,
+    Median,
+    Max,
+    Min,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Value(Expression),
+    Range(Expression, Expression),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -27,25 +142,86 @@ pub enum AstNode {
     Definition {
         ident: String,
         expr: Expression,
+        kind: Option<Type>,
+    },
+    Constant {
+        ident: String,
+        expr: Expression,
+        kind: Option<Type>,
+    },
+    Destructure {
+        idents: Vec<String>,
+        expr: Expression,
     },
     If {
         comp: Expression,
+        source: String,
         block: Vec<Box<AstNode>>,
         senao: Option<Vec<Box<AstNode>>>,
     },
     While {
         comp: Expression,
+        source: String,
         block: Vec<Box<AstNode>>,
     },
     Function {
         ident: String,
         args: Vec<String>,
+        types: Vec<Option<Type>>,
+        defaults: Vec<Option<Expression>>,
+        returns: Option<Type>,
         block: Vec<Box<AstNode>>,
     },
     FnCall {
         ident: String,
         vars: Vec<Expression>,
+        named: Vec<(String, Expression)>,
+        line: usize,
     },
     Return(Expression),
     Expression(Expression),
+    Seed(Expression),
+    DefineKey {
+        ident: String,
+        key: Expression,
+        expr: Expression,
+    },
+    RemoveKey {
+        ident: String,
+        key: Expression,
+    },
+    ForEach {
+        ident: String,
+        iterable: Expression,
+        block: Vec<Box<AstNode>>,
+    },
+    Record {
+        ident: String,
+        fields: Vec<String>,
+    },
+    Try {
+        block: Vec<Box<AstNode>>,
+        ident: Option<String>,
+        catch: Vec<Box<AstNode>>,
+    },
+    Throw(Expression),
+    Import {
+        path: String,
+        variables: bool,
+    },
+    Match {
+        expr: Expression,
+        cases: Vec<(Vec<Pattern>, Vec<Box<AstNode>>)>,
+        default: Option<Vec<Box<AstNode>>>,
+    },
+    Assert {
+        comp: Expression,
+        source: String,
+        message: Option<Expression>,
+    },
+    DefineField {
+        ident: String,
+        field: String,
+        expr: Expression,
+    },
 }
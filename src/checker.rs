@@ -0,0 +1,659 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    nested, AstNode, ConvertType, Error, Expression, InputType, InterpreterError, ListFn, MathFn,
+    Pattern, Type, TypeError, VoidSource,
+};
+
+pub fn is_constant_name(ident: &str) -> bool {
+    ident.chars().any(char::is_alphabetic) && !ident.chars().any(char::is_lowercase)
+}
+
+pub fn check_constants(program: &[Box<AstNode>], uppercase: bool) -> Result<(), Error> {
+    check_block(program, &mut HashSet::new(), uppercase)
+}
+
+fn assign(ident: &str, constants: &HashSet<String>) -> Result<(), Error> {
+    if constants.contains(ident) {
+        Err(InterpreterError::ConstantReassignment(ident.into()).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_block(
+    block: &[Box<AstNode>],
+    constants: &mut HashSet<String>,
+    uppercase: bool,
+) -> Result<(), Error> {
+    nested(|| check_statements(block, constants, uppercase))
+}
+
+fn check_statements(
+    block: &[Box<AstNode>],
+    constants: &mut HashSet<String>,
+    uppercase: bool,
+) -> Result<(), Error> {
+    for node in block {
+        match node.as_ref() {
+            AstNode::Constant { ident, .. } => {
+                assign(ident, constants)?;
+                constants.insert(ident.clone());
+            }
+            AstNode::Definition { ident, .. } => {
+                assign(ident, constants)?;
+                if uppercase && is_constant_name(ident) {
+                    constants.insert(ident.clone());
+                }
+            }
+            AstNode::Destructure { idents, .. } => {
+                for ident in idents {
+                    assign(ident, constants)?;
+                }
+            }
+            AstNode::DefineKey { ident, .. }
+            | AstNode::RemoveKey { ident, .. }
+            | AstNode::DefineField { ident, .. } => assign(ident, constants)?,
+            AstNode::If { block, senao, .. } => {
+                check_block(block, &mut constants.clone(), uppercase)?;
+                if let Some(senao) = senao {
+                    check_block(senao, &mut constants.clone(), uppercase)?;
+                }
+            }
+            AstNode::While { block, .. } => check_block(block, &mut constants.clone(), uppercase)?,
+            AstNode::ForEach { ident, block, .. } => {
+                assign(ident, constants)?;
+                check_block(block, &mut constants.clone(), uppercase)?;
+            }
+            AstNode::Try {
+                block,
+                ident,
+                catch,
+            } => {
+                check_block(block, &mut constants.clone(), uppercase)?;
+                if let Some(ident) = ident {
+                    assign(ident, constants)?;
+                }
+                check_block(catch, &mut constants.clone(), uppercase)?;
+            }
+            AstNode::Match { cases, default, .. } => {
+                for (_, block) in cases {
+                    check_block(block, &mut constants.clone(), uppercase)?;
+                }
+                if let Some(default) = default {
+                    check_block(default, &mut constants.clone(), uppercase)?;
+                }
+            }
+            AstNode::Function { block, .. } => check_block(block, &mut HashSet::new(), uppercase)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Signature {
+    args: Vec<String>,
+    types: Vec<Option<Type>>,
+    defaults: Vec<bool>,
+    returns: Option<Type>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Env {
+    variables: HashMap<String, Type>,
+    declared: HashMap<String, Type>,
+}
+
+impl Env {
+    fn merge(&mut self, other: Env) {
+        for (ident, kind) in other.variables {
+            match self.variables.get(&ident) {
+                Some(known) if *known != kind => {
+                    self.variables.insert(ident, Type::Any);
+                }
+                Some(_) => {}
+                None => {
+                    self.variables.insert(ident, kind);
+                }
+            }
+        }
+    }
+}
+
+struct Checker {
+    functions: HashMap<String, Option<Signature>>,
+}
+
+pub fn check_types(program: &[Box<AstNode>]) -> Result<(), Error> {
+    let mut functions = HashMap::new();
+    collect_functions(program, &mut functions);
+    Checker { functions }.check_block(program, &mut Env::default(), None)
+}
+
+fn collect_functions(block: &[Box<AstNode>], functions: &mut HashMap<String, Option<Signature>>) {
+    for node in block {
+        match node.as_ref() {
+            AstNode::Function {
+                ident,
+                args,
+                types,
+                defaults,
+                returns,
+                block,
+            } => {
+                let signature = Signature {
+                    args: args.clone(),
+                    types: types.clone(),
+                    defaults: defaults.iter().map(Option::is_some).collect(),
+                    returns: returns.clone(),
+                };
+                match functions.get(ident) {
+                    Some(Some(known)) if *known != signature => {
+                        functions.insert(ident.clone(), None);
+                    }
+                    Some(_) => {}
+                    None => {
+                        functions.insert(ident.clone(), Some(signature));
+                    }
+                }
+                collect_functions(block, functions);
+            }
+            AstNode::If { block, senao, .. } => {
+                collect_functions(block, functions);
+                if let Some(senao) = senao {
+                    collect_functions(senao, functions);
+                }
+            }
+            AstNode::While { block, .. } | AstNode::ForEach { block, .. } => {
+                collect_functions(block, functions)
+            }
+            AstNode::Try { block, catch, .. } => {
+                collect_functions(block, functions);
+                collect_functions(catch, functions);
+            }
+            AstNode::Match { cases, default, .. } => {
+                for (_, block) in cases {
+                    collect_functions(block, functions);
+                }
+                if let Some(default) = default {
+                    collect_functions(default, functions);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Checker {
+    fn check_block(
+        &self,
+        block: &[Box<AstNode>],
+        env: &mut Env,
+        function: Option<(&str, &Option<Type>)>,
+    ) -> Result<(), Error> {
+        nested(|| self.check_statements(block, env, function))
+    }
+
+    fn check_statements(
+        &self,
+        block: &[Box<AstNode>],
+        env: &mut Env,
+        function: Option<(&str, &Option<Type>)>,
+    ) -> Result<(), Error> {
+        for node in block {
+            match node.as_ref() {
+                AstNode::Definition { ident, expr, kind }
+                | AstNode::Constant { ident, expr, kind } => {
+                    let found = self.infer(expr, env)?;
+                    if let Some(kind) = kind {
+                        env.declared.insert(ident.clone(), kind.clone());
+                    }
+                    let kind = match env.declared.get(ident) {
+                        Some(declared) if !declared.accepts(&found) => {
+                            return Err(TypeError::VariableType(
+                                ident.clone(),
+                                declared.name(),
+                                found.name(),
+                            )
+                            .into())
+                        }
+                        Some(declared) => declared.clone(),
+                        None => found,
+                    };
+                    env.variables.insert(ident.clone(), kind);
+                }
+                AstNode::Destructure { idents, expr } => {
+                    self.infer(expr, env)?;
+                    for ident in idents {
+                        let kind = env.declared.get(ident).cloned().unwrap_or(Type::Any);
+                        env.variables.insert(ident.clone(), kind);
+                    }
+                }
+                AstNode::Print(exprs) => {
+                    for expr in exprs {
+                        self.infer(expr, env)?;
+                    }
+                }
+                AstNode::If {
+                    comp, block, senao, ..
+                } => {
+                    self.infer(comp, env)?;
+                    let mut nested = env.clone();
+                    self.check_block(block, &mut nested, function)?;
+                    env.merge(nested);
+                    if let Some(senao) = senao {
+                        let mut nested = env.clone();
+                        self.check_block(senao, &mut nested, function)?;
+                        env.merge(nested);
+                    }
+                }
+                AstNode::While { comp, block, .. } => {
+                    self.infer(comp, env)?;
+                    let mut nested = env.clone();
+                    self.check_block(block, &mut nested, function)?;
+                    env.merge(nested);
+                }
+                AstNode::ForEach {
+                    ident,
+                    iterable,
+                    block,
+                } => {
+                    match self.infer(iterable, env)? {
+                        Type::Any | Type::List | Type::Dictionary => {}
+                        kind => {
+                            return Err(TypeError::NotAList("iteration".into(), kind.name()).into())
+                        }
+                    }
+                    let mut nested = env.clone();
+                    nested.variables.insert(ident.clone(), Type::Any);
+                    self.check_block(block, &mut nested, function)?;
+                    env.merge(nested);
+                }
+                AstNode::Function {
+                    ident,
+                    args,
+                    types,
+                    defaults,
+                    returns,
+                    block,
+                } => {
+                    let mut nested = Env::default();
+                    for (arg, kind) in args.iter().zip(types) {
+                        let kind = kind.clone().unwrap_or(Type::Any);
+                        nested.variables.insert(arg.clone(), kind.clone());
+                        nested.declared.insert(arg.clone(), kind);
+                    }
+                    for ((arg, kind), default) in args.iter().zip(types).zip(defaults) {
+                        if let Some(default) = default {
+                            let found = self.infer(default, &nested)?;
+                            if let Some(kind) = kind {
+                                if !kind.accepts(&found) {
+                                    return Err(TypeError::ArgumentType(
+                                        arg.clone(),
+                                        ident.clone(),
+                                        kind.name(),
+                                        found.name(),
+                                    )
+                                    .into());
+                                }
+                            }
+                        }
+                    }
+                    self.check_block(block, &mut nested, Some((ident, returns)))?;
+                }
+                AstNode::FnCall {
+                    ident, vars, named, ..
+                } => {
+                    self.check_call(ident, vars, named, env)?;
+                }
+                AstNode::Return(expr) => {
+                    let found = self.infer(expr, env)?;
+                    if let Some((ident, Some(returns))) = function {
+                        if !returns.accepts(&found) {
+                            return Err(TypeError::ReturnType(
+                                ident.into(),
+                                returns.name(),
+                                found.name(),
+                            )
+                            .into());
+                        }
+                    }
+                }
+                AstNode::Expression(expr) | AstNode::Seed(expr) | AstNode::Throw(expr) => {
+                    self.infer(expr, env)?;
+                }
+                AstNode::DefineKey { key, expr, .. } => {
+                    self.infer(key, env)?;
+                    self.infer(expr, env)?;
+                }
+                AstNode::RemoveKey { key, .. } => {
+                    self.infer(key, env)?;
+                }
+                AstNode::DefineField { expr, .. } => {
+                    self.infer(expr, env)?;
+                }
+                AstNode::Try {
+                    block,
+                    ident,
+                    catch,
+                } => {
+                    // Errors inside tente are handled by the program itself
+                    let mut nested = env.clone();
+                    if self.check_block(block, &mut nested, function).is_ok() {
+                        env.merge(nested);
+                    }
+                    let mut nested = env.clone();
+                    if let Some(ident) = ident {
+                        nested
+                            .variables
+                            .insert(ident.clone(), Type::Record("Erro".into()));
+                    }
+                    self.check_block(catch, &mut nested, function)?;
+                    env.merge(nested);
+                }
+                AstNode::Match {
+                    expr,
+                    cases,
+                    default,
+                } => {
+                    self.infer(expr, env)?;
+                    for (patterns, block) in cases {
+                        for pattern in patterns {
+                            match pattern {
+                                Pattern::Value(value) => {
+                                    self.infer(value, env)?;
+                                }
+                                Pattern::Range(start, end) => {
+                                    self.infer(start, env)?;
+                                    self.infer(end, env)?;
+                                }
+                            }
+                        }
+                        let mut nested = env.clone();
+                        self.check_block(block, &mut nested, function)?;
+                        env.merge(nested);
+                    }
+                    if let Some(default) = default {
+                        let mut nested = env.clone();
+                        self.check_block(default, &mut nested, function)?;
+                        env.merge(nested);
+                    }
+                }
+                AstNode::Assert { comp, message, .. } => {
+                    self.infer(comp, env)?;
+                    if let Some(message) = message {
+                        self.infer(message, env)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn check_call(
+        &self,
+        ident: &str,
+        vars: &[Expression],
+        named: &[(String, Expression)],
+        env: &Env,
+    ) -> Result<Type, Error> {
+        let mut found = vec![];
+        for var in vars {
+            found.push(self.infer(var, env)?);
+        }
+        let mut found_named = vec![];
+        for (arg, var) in named {
+            found_named.push((arg, self.infer(var, env)?));
+        }
+        let signature = match self.functions.get(ident) {
+            Some(Some(signature)) if !env.variables.contains_key(ident) => signature,
+            _ => return Ok(Type::Any),
+        };
+        if found.len() > signature.args.len() {
+            return Err(InterpreterError::WrongNumberOfArgs(
+                ident.into(),
+                signature.types.len(),
+                found.len(),
+                {
+                    if found.len() == 1 {
+                        "was"
+                    } else {
+                        "were"
+                    }
+                }
+                .to_string(),
+            )
+            .into());
+        }
+        let mut given: Vec<(&String, Type)> = signature.args.iter().zip(found).collect();
+        for (arg, found) in found_named {
+            match signature.args.iter().find(|known| *known == arg) {
+                None => {
+                    return Err(InterpreterError::UnknownArgument(ident.into(), arg.clone()).into())
+                }
+                Some(known) if given.iter().any(|(given, _)| *given == known) => {
+                    return Err(
+                        InterpreterError::RepeatedArgument(ident.into(), arg.clone()).into(),
+                    )
+                }
+                Some(known) => given.push((known, found)),
+            }
+        }
+        let missing: Vec<String> = signature
+            .args
+            .iter()
+            .zip(&signature.defaults)
+            .filter(|(arg, default)| !**default && !given.iter().any(|(given, _)| given == arg))
+            .map(|(arg, _)| arg.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(InterpreterError::MissingArguments(ident.into(), missing).into());
+        }
+        for (arg, found) in given {
+            let position = signature.args.iter().position(|known| known == arg);
+            if let Some(Some(kind)) = position.map(|position| &signature.types[position]) {
+                if !kind.accepts(&found) {
+                    return Err(TypeError::ArgumentType(
+                        arg.clone(),
+                        ident.into(),
+                        kind.name(),
+                        found.name(),
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(signature.returns.clone().unwrap_or(Type::Any))
+    }
+
+    fn infer(&self, expr: &Expression, env: &Env) -> Result<Type, Error> {
+        Ok(match expr {
+            // A variable holding nada may be guarded by a condition, so only runtime can tell
+            Expression::Variable(ident) => match env.variables.get(ident) {
+                Some(Type::Void) | None => Type::Any,
+                Some(kind) => kind.clone(),
+            },
+            Expression::Value(value) => Type::of(value),
+            Expression::Sum(left, right) => {
+                arithmetic("addition", self.infer(left, env)?, self.infer(right, env)?)?
+            }
+            Expression::Sub(left, right) => arithmetic(
+                "subtraction",
+                self.infer(left, env)?,
+                self.infer(right, env)?,
+            )?,
+            Expression::Mult(left, right) => arithmetic(
+                "multiplication",
+                self.infer(left, env)?,
+                self.infer(right, env)?,
+            )?,
+            Expression::Div(left, right) => {
+                arithmetic("division", self.infer(left, env)?, self.infer(right, env)?)?
+            }
+            Expression::Is(left, right)
+            | Expression::IsNot(left, right)
+            | Expression::Smlr(left, right)
+            | Expression::Bigr(left, right)
+            | Expression::SmlrEq(left, right)
+            | Expression::BigrEq(left, right)
+            | Expression::And(left, right)
+            | Expression::Or(left, right) => {
+                self.infer(left, env)?;
+                self.infer(right, env)?;
+                Type::Bool
+            }
+            Expression::FnCall(ident, vars, named, _) => {
+                self.check_call(ident, vars, named, env)?
+            }
+            Expression::Entrada(kind, prompt) => {
+                if let Some(prompt) = prompt {
+                    self.infer(prompt, env)?;
+                }
+                match kind {
+                    InputType::Number => Type::Number,
+                    InputType::Integer => Type::Integer,
+                    InputType::String => Type::String,
+                    InputType::Bool => Type::Bool,
+                    InputType::NumberList => Type::List,
+                }
+            }
+            Expression::Math(function, args) => {
+                for arg in args {
+                    match self.infer(arg, env)? {
+                        Type::Any | Type::Void | Type::Number | Type::Integer | Type::Float => {}
+                        kind => {
+                            return Err(
+                                TypeError::NotANumber(function.name().into(), kind.name()).into()
+                            )
+                        }
+                    }
+                }
+                match function {
+                    MathFn::Floor | MathFn::Ceil => Type::Integer,
+                    _ => Type::Number,
+                }
+            }
+            Expression::Tuple(items) => {
+                for item in items {
+                    self.infer(item, env)?;
+                }
+                Type::Tuple
+            }
+            Expression::List(items) => {
+                for item in items {
+                    self.infer(item, env)?;
+                }
+                Type::List
+            }
+            Expression::RandomNumber(start, end) => {
+                self.infer(start, env)?;
+                self.infer(end, env)?;
+                Type::Number
+            }
+            Expression::RandomItem(list) => {
+                self.infer(list, env)?;
+                Type::Any
+            }
+            Expression::Convert(value, to) => {
+                self.infer(value, env)?;
+                match to {
+                    ConvertType::Number => Type::Number,
+                    ConvertType::Integer => Type::Integer,
+                    ConvertType::String => Type::String,
+                }
+            }
+            Expression::TypeOf(value) => {
+                self.infer(value, env)?;
+                Type::String
+            }
+            Expression::Dictionary(entries) => {
+                for (key, value) in entries {
+                    self.infer(key, env)?;
+                    self.infer(value, env)?;
+                }
+                Type::Dictionary
+            }
+            Expression::Lookup(_, key) => {
+                self.infer(key, env)?;
+                Type::Any
+            }
+            Expression::HasKey(_, key) => {
+                self.infer(key, env)?;
+                Type::Bool
+            }
+            Expression::NewRecord(ident, fields) => {
+                for field in fields {
+                    self.infer(field, env)?;
+                }
+                Type::Record(ident.clone())
+            }
+            Expression::Field(_, record) => {
+                self.infer(record, env)?;
+                Type::Any
+            }
+            Expression::AnonymousFunction(args, block) => {
+                let mut nested = Env::default();
+                for arg in args {
+                    nested.variables.insert(arg.clone(), Type::Any);
+                }
+                self.check_block(block, &mut nested, None)?;
+                Type::Function
+            }
+            Expression::ListOp(function, args) => {
+                let mut args = args.iter();
+                if let Some(list) = args.next() {
+                    match self.infer(list, env)? {
+                        Type::Any | Type::List => {}
+                        kind => {
+                            return Err(
+                                TypeError::NotAList(function.name().into(), kind.name()).into()
+                            )
+                        }
+                    }
+                }
+                for arg in args {
+                    self.infer(arg, env)?;
+                }
+                match function {
+                    ListFn::Map | ListFn::Filter | ListFn::Sort => Type::List,
+                    ListFn::Any | ListFn::All => Type::Bool,
+                    ListFn::Mean | ListFn::Median => Type::Float,
+                    ListFn::Reduce | ListFn::Sum | ListFn::Max | ListFn::Min => Type::Any,
+                }
+            }
+        })
+    }
+}
+
+fn arithmetic(operation: &str, left: Type, right: Type) -> Result<Type, Error> {
+    let illegal = |kind: &Type| {
+        Err(TypeError::IllegalOperation(operation.into(), "any".into(), kind.name()).into())
+    };
+    for kind in [&right, &left] {
+        if let Type::List | Type::Dictionary | Type::Record(_) | Type::Function = kind {
+            return illegal(kind);
+        }
+    }
+    for kind in [&right, &left] {
+        if let Type::Bool = kind {
+            return illegal(kind);
+        }
+    }
+    for kind in [&right, &left] {
+        if let (Type::String, false) = (kind, operation == "addition") {
+            return illegal(kind);
+        }
+    }
+    Ok(match (left, right) {
+        (Type::Void, _) | (_, Type::Void) => {
+            return Err(TypeError::VoidOperation(operation.into(), VoidSource::Value).into())
+        }
+        (Type::String, _) | (_, Type::String) => Type::String,
+        (Type::Any, _) | (_, Type::Any) => Type::Any,
+        (Type::Integer, Type::Integer) => Type::Integer,
+        (Type::Float, _) | (_, Type::Float) => Type::Float,
+        _ => Type::Number,
+    })
+}
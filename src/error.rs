@@ -1,12 +1,17 @@
-use std::fmt::Display;
+use std::{fmt::Display, io::ErrorKind};
 
 use thiserror::Error;
 
+/// Frames shown in a stack trace before the ones in the middle are hidden
+const TRACE_LENGTH: usize = 20;
+
 #[derive(Error, Debug)]
 pub enum Error {
     InterpreterError(InterpreterError),
     TypeError(TypeError),
     ParserError(ParserError),
+    /// An error and the function calls it went through, from the innermost
+    Traced(Box<Error>, Vec<Frame>),
 }
 
 impl Display for Error {
@@ -15,7 +20,52 @@ impl Display for Error {
             Error::InterpreterError(error) => write!(f, "{}", error),
             Error::TypeError(error) => write!(f, "{}", error),
             Error::ParserError(error) => write!(f, "{}", error),
+            Error::Traced(error, frames) => {
+                write!(f, "{}", error)?;
+                for frame in trace(frames) {
+                    write!(f, "\n{}", frame)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    pub function: String,
+    pub line: Option<usize>,
+    pub args: Vec<String>,
+    /// Calls made with `retorne função` that this frame took the place of
+    pub tail_calls: usize,
+}
+
+/// Where the `nada` used in an operation came from
+#[derive(Debug)]
+pub enum VoidSource {
+    /// A value written in the program, or one the operation found by itself
+    Value,
+    /// A variable, which may hold what a function returned
+    Variable,
+    /// A call to a function that returned nothing
+    Call(String),
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "em função {}", self.function)?;
+        if let Some(line) = self.line {
+            write!(f, " (linha {})", line)?;
+        }
+        if !self.args.is_empty() {
+            write!(f, " usando {}", self.args.join(", "))?;
         }
+        match self.tail_calls {
+            0 => {}
+            1 => write!(f, " (depois de 1 chamada de cauda)")?,
+            calls => write!(f, " (depois de {} chamadas de cauda)", calls)?,
+        }
+        Ok(())
     }
 }
 
@@ -33,28 +83,304 @@ macro_rules! impl_error {
 
 impl_error!(InterpreterError, TypeError, ParserError);
 
+impl Error {
+    /// Adds a function call the error went through to its stack trace
+    pub fn called(self, frame: Frame) -> Error {
+        match self {
+            Error::Traced(error, mut frames) => {
+                frames.push(frame);
+                Error::Traced(error, frames)
+            }
+            error => Error::Traced(Box::new(error), vec![frame]),
+        }
+    }
+
+    /// The error without its stack trace
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::Traced(error, _) => error.root(),
+            error => error,
+        }
+    }
+
+    pub fn mensagem(&self) -> String {
+        match self {
+            Error::Traced(error, _) => error.mensagem(),
+            Error::InterpreterError(error) => match error {
+                InterpreterError::UndefinedVariable(ident) => {
+                    format!("A variável \"{}\" não foi definida", ident)
+                }
+                InterpreterError::UndefinedFunction(ident) => {
+                    format!("A função \"{}\" não foi definida", ident)
+                }
+                InterpreterError::UndefinedRecord(ident) => {
+                    format!("O registro \"{}\" não foi definido", ident)
+                }
+                InterpreterError::ParseError(value, to) => {
+                    format!("Não foi possível ler {} como {}", value, nome(to))
+                }
+                InterpreterError::WrongNumberOfArgs(ident, expected, supplied, _) => format!(
+                    "A função {} esperava {} argumentos mas recebeu {}",
+                    ident, expected, supplied
+                ),
+                InterpreterError::MissingArguments(ident, args) => format!(
+                    "A função {} precisa {}",
+                    ident,
+                    missing(args, "do argumento", "dos argumentos", "e")
+                ),
+                InterpreterError::UnknownArgument(ident, arg) => {
+                    format!("A função {} não tem o argumento \"{}\"", ident, arg)
+                }
+                InterpreterError::RepeatedArgument(ident, arg) => format!(
+                    "O argumento \"{}\" da função {} foi passado mais de uma vez",
+                    arg, ident
+                ),
+                InterpreterError::WrongNumberOfValues(expected, found) => {
+                    format!("Eram esperados {} valores mas vieram {}", expected, found)
+                }
+                InterpreterError::WrongNumberOfFields(ident, expected, supplied, _) => format!(
+                    "O registro {} esperava {} campos mas recebeu {}",
+                    ident, expected, supplied
+                ),
+                InterpreterError::InputError => "Não foi possível ler a entrada".into(),
+                InterpreterError::EndOfInput => "A entrada chegou ao fim".into(),
+                InterpreterError::RecursionLimit(limit) => {
+                    format!("O limite de recursão de {} foi atingido", limit)
+                }
+                InterpreterError::MemoryLimit(limit) => {
+                    format!("O limite de memória de {} MiB foi atingido", limit)
+                }
+                InterpreterError::StepLimit(limit) => {
+                    format!("O limite de {} passos foi atingido", limit)
+                }
+                InterpreterError::TimeLimit(limit) => {
+                    format!("O limite de tempo de {} segundos foi atingido", limit)
+                }
+                InterpreterError::MathDomain(function, value) => format!(
+                    "Não é possível calcular {} de {}",
+                    operacao(function),
+                    value
+                ),
+                InterpreterError::EmptyList(function) => format!(
+                    "Não é possível calcular {} de uma lista vazia",
+                    operacao(function)
+                ),
+                InterpreterError::Overflow(operation) => format!(
+                    "O resultado de {} não cabe em um inteiro",
+                    operacao(operation)
+                ),
+                InterpreterError::DivisionByZero => {
+                    "Não é possível dividir um inteiro por zero".into()
+                }
+                InterpreterError::AssertionFailed(source, values, message) => format!(
+                    "A verificação \"{}\" falhou{}",
+                    source,
+                    details(values, message, "com")
+                ),
+                InterpreterError::ConstantReassignment(ident) => {
+                    format!("Não é possível alterar a constante \"{}\"", ident)
+                }
+                InterpreterError::ImportError(path, reason) => format!(
+                    "Não foi possível importar \"{}\": {}",
+                    path,
+                    motivo(*reason)
+                ),
+                InterpreterError::CyclicImport(path) => {
+                    format!("Importação cíclica de \"{}\"", path)
+                }
+                InterpreterError::ImportedFile(path, error) => {
+                    format!("No arquivo importado \"{}\": {}", path, error.mensagem())
+                }
+                InterpreterError::MissingKey(key) => {
+                    format!("A chave \"{}\" não existe no dicionário", key)
+                }
+                InterpreterError::UserError(message) => message.clone(),
+            },
+            Error::TypeError(error) => match error {
+                TypeError::IllegalOperation(operation, left, right) => format!(
+                    "Não é possível fazer {} entre os tipos {} e {}",
+                    operacao(operation),
+                    nome(left),
+                    nome(right)
+                ),
+                TypeError::VoidOperation(operation, source) => format!(
+                    "Não é possível fazer {} com nada{}",
+                    operacao(operation),
+                    returned(
+                        source,
+                        "a função",
+                        "não retornou nenhum valor",
+                        "talvez uma função usada nela não tenha retornado nenhum valor"
+                    )
+                ),
+                TypeError::ToBoolError(value, source) => format!(
+                    "Não é possível tratar {} como lógico{}",
+                    nome(value),
+                    condition(source, "na condição")
+                ),
+                TypeError::NotANumber(operation, kind) => format!(
+                    "Não é possível fazer {} com o tipo {}",
+                    operacao(operation),
+                    nome(kind)
+                ),
+                TypeError::NotAList(operation, kind) => format!(
+                    "Não é possível calcular {} do tipo {}, era esperada uma lista",
+                    operacao(operation),
+                    nome(kind)
+                ),
+                TypeError::NotADictionary(operation, kind) => format!(
+                    "Não é possível fazer {} com o tipo {}, era esperado um dicionário",
+                    operacao(operation),
+                    nome(kind)
+                ),
+                TypeError::UnknownField(field, record) => {
+                    format!("O registro {} não tem o campo \"{}\"", record, field)
+                }
+                TypeError::NotARecord(field, kind) => format!(
+                    "Não é possível acessar o campo \"{}\" do tipo {}, era esperado um registro",
+                    field,
+                    nome(kind)
+                ),
+                TypeError::NotAFunction(operation, kind) => format!(
+                    "Não é possível fazer {} com o tipo {}, era esperada uma função",
+                    operacao(operation),
+                    nome(kind)
+                ),
+                TypeError::VariableType(ident, expected, found) => format!(
+                    "A variável \"{}\" foi declarada como {} mas recebeu {}",
+                    ident,
+                    nome(expected),
+                    nome(found)
+                ),
+                TypeError::ArgumentType(arg, function, expected, found) => format!(
+                    "O argumento \"{}\" da função {} espera {} mas recebeu {}",
+                    arg,
+                    function,
+                    nome(expected),
+                    nome(found)
+                ),
+                TypeError::ReturnType(function, expected, found) => format!(
+                    "A função {} deveria retornar {} mas retorna {}",
+                    function,
+                    nome(expected),
+                    nome(found)
+                ),
+            },
+            Error::ParserError(error) => match error {
+                ParserError::PestError(reason) => format!("A leitura falhou:\n{}", reason),
+                ParserError::NotAnExpression(expr) => format!("Não é uma expressão:\n{}", expr),
+                ParserError::NotAST(expr) => format!("Sintaxe incorreta:\n{}", expr),
+                ParserError::ParseError(value, to) => {
+                    format!("Não foi possível ler {} como {}", value, nome(to))
+                }
+                ParserError::IncompleteExpr(expr, part) => {
+                    format!("Falta {} da expressão {}", parte(part), expr)
+                }
+                ParserError::IncompleteFnCall(call, missing) => {
+                    format!("Falta {} na chamada de função {}", parte(missing), call)
+                }
+                ParserError::UnsupportedOperator(operator) => {
+                    format!("O operador {} não é suportado", operator)
+                }
+            },
+        }
+    }
+
+    pub fn categoria(&self) -> &'static str {
+        match self {
+            Error::Traced(error, _) => error.categoria(),
+            Error::InterpreterError(InterpreterError::UserError(_)) => "usuário",
+            Error::InterpreterError(_) => "execução",
+            Error::TypeError(_) => "tipo",
+            Error::ParserError(_) => "sintaxe",
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum InterpreterError {
     #[error("Variable \"{0}\" not defined")]
     UndefinedVariable(String),
     #[error("Function \"{0}\" not defined")]
     UndefinedFunction(String),
+    #[error("Record \"{0}\" not defined")]
+    UndefinedRecord(String),
     #[error("Couldn't parse {0} as a {1}")]
     ParseError(String, String),
     #[error("Function {0} expected {1} arguments but {2} {3} supplied")]
     WrongNumberOfArgs(String, usize, usize, String),
+    #[error("Function {0} is missing {}", missing(.1, "the argument", "the arguments", "and"))]
+    MissingArguments(String, Vec<String>),
+    #[error("Function {0} has no argument \"{1}\"")]
+    UnknownArgument(String, String),
+    #[error("Argument \"{1}\" of function {0} was given more than once")]
+    RepeatedArgument(String, String),
+    #[error("Expected {0} values but got {1}")]
+    WrongNumberOfValues(usize, usize),
+    #[error("Record {0} expected {1} fields but {2} {3} supplied")]
+    WrongNumberOfFields(String, usize, usize, String),
     #[error("Failed to read input")]
     InputError,
+    #[error("Reached the end of the input")]
+    EndOfInput,
     #[error("Reached recursion limit of {0}")]
     RecursionLimit(usize),
+    #[error("Reached memory limit of {0} MiB")]
+    MemoryLimit(usize),
+    #[error("Reached step limit of {0}")]
+    StepLimit(usize),
+    #[error("Reached time limit of {0} seconds")]
+    TimeLimit(f64),
+    #[error("Cannot compute {0} of {1}")]
+    MathDomain(String, String),
+    #[error("Cannot take {0} of an empty list")]
+    EmptyList(String),
+    #[error("Result of {0} is too large for an integer")]
+    Overflow(String),
+    #[error("Cannot divide an integer by zero")]
+    DivisionByZero,
+    #[error("Key \"{0}\" not found in dictionary")]
+    MissingKey(String),
+    #[error("{0}")]
+    UserError(String),
+    #[error("Cannot reassign constant \"{0}\"")]
+    ConstantReassignment(String),
+    #[error("Couldn't import \"{0}\": {1}")]
+    ImportError(String, ErrorKind),
+    #[error("Cyclic import of \"{0}\"")]
+    CyclicImport(String),
+    #[error("In imported file \"{0}\": {1}")]
+    ImportedFile(String, Box<Error>),
+    #[error("Assertion \"{0}\" failed{}", details(.1, .2, "with"))]
+    AssertionFailed(String, Vec<(String, String)>, Option<String>),
 }
 
 #[derive(Error, Debug)]
 pub enum TypeError {
     #[error("Cannot perform {0}, between types {1} and {2}")]
     IllegalOperation(String, String, String),
-    #[error("Cannot treat {0} as a Bool type")]
-    ToBoolError(String),
+    #[error("Cannot treat {0} as a Bool type{}", condition(.1, "in condition"))]
+    ToBoolError(String, Option<String>),
+    #[error("Cannot perform {0} on type {1}")]
+    NotANumber(String, String),
+    #[error("Cannot take {0} of type {1}, expected a list")]
+    NotAList(String, String),
+    #[error("Cannot perform {0} on type {1}, expected a dictionary")]
+    NotADictionary(String, String),
+    #[error("Record {1} has no field \"{0}\"")]
+    UnknownField(String, String),
+    #[error("Cannot access field \"{0}\" of type {1}, expected a record")]
+    NotARecord(String, String),
+    #[error("Cannot perform {0} with nada{}", returned(.1, "function", "returned nothing", "a function used in it may have returned nothing"))]
+    VoidOperation(String, VoidSource),
+    #[error("Cannot perform {0} with type {1}, expected a function")]
+    NotAFunction(String, String),
+    #[error("Variable \"{0}\" was declared as {1} but got {2}")]
+    VariableType(String, String, String),
+    #[error("Argument \"{0}\" of function {1} expects {2} but got {3}")]
+    ArgumentType(String, String, String, String),
+    #[error("Function {0} should return {1} but returns {2}")]
+    ReturnType(String, String, String),
 }
 
 #[derive(Error, Debug)]
@@ -74,3 +400,163 @@ pub enum ParserError {
     #[error("Operator {0} is not supported")]
     UnsupportedOperator(String),
 }
+
+fn nome(kind: &str) -> &str {
+    match kind {
+        "number" => "número",
+        "integer" | "Integer" => "inteiro",
+        "float" | "Float" => "real",
+        "string" | "String" => "texto",
+        "bool" => "lógico",
+        "list" | "List" => "lista",
+        "dictionary" | "Dictionary" => "dicionário",
+        "record" => "registro",
+        "function" | "Function" => "função",
+        "tuple" | "Tuple" => "valores",
+        "void" | "Void" => "nada",
+        "any" => "qualquer",
+        "seed" => "semente",
+        "yes or no answer" => "resposta de sim ou não",
+        "type" => "tipo",
+        kind => kind,
+    }
+}
+
+fn operacao(operation: &str) -> &str {
+    match operation {
+        "addition" => "adição",
+        "subtraction" => "subtração",
+        "multiplication" => "multiplicação",
+        "division" => "divisão",
+        "comparison" => "comparação",
+        "iteration" => "iteração",
+        "seeding" => "semente",
+        "key lookup" => "busca de chave",
+        "key membership test" => "teste de chave",
+        "key insertion" => "inserção de chave",
+        "key removal" => "remoção de chave",
+        "random item" => "item aleatório",
+        "random number" => "número aleatório",
+        "square root" => "raiz quadrada",
+        "exponentiation" => "potência",
+        "rounding" => "arredondamento",
+        "rounding to decimal places" => "arredondamento com casas decimais",
+        "floor" => "piso",
+        "ceiling" => "teto",
+        "absolute value" => "valor absoluto",
+        "sine" => "seno",
+        "cosine" => "cosseno",
+        "minimum" => "mínimo",
+        "maximum" => "máximo",
+        "mapping" => "mapeamento",
+        "filtering" => "filtragem",
+        "reduction" => "redução",
+        "sorting" => "ordenação",
+        "any test" => "teste de algum",
+        "all test" => "teste de todos",
+        "sum" => "soma",
+        "mean" => "média",
+        "median" => "mediana",
+        operation => operation,
+    }
+}
+
+fn parte(part: &str) -> &str {
+    match part {
+        "left" => "o valor da esquerda",
+        "right" => "o valor da direita",
+        "value" => "o valor",
+        "identifier" => "o identificador",
+        "arguments" | "parameters" => "os argumentos",
+        "parameter" => "o argumento",
+        "block" | "body" => "o bloco",
+        "condition" => "a condição",
+        "field" => "o campo",
+        "key" => "a chave",
+        "iterable" => "a lista",
+        "list" => "a lista",
+        "dictionary" => "o dicionário",
+        "record" => "o registro",
+        "type" => "o tipo",
+        "path" => "o caminho",
+        "lower" => "o menor valor",
+        "upper" => "o maior valor",
+        "converted" | "inspected" => "o valor",
+        "operator" => "o operador",
+        "statement" | "definition" => "o comando",
+        part => part,
+    }
+}
+
+fn motivo(reason: ErrorKind) -> &'static str {
+    match reason {
+        ErrorKind::NotFound => "o arquivo não existe",
+        ErrorKind::PermissionDenied => "sem permissão para ler o arquivo",
+        _ => "o arquivo não pôde ser lido",
+    }
+}
+
+fn trace(frames: &[Frame]) -> Vec<String> {
+    let mut calls: Vec<(&Frame, usize)> = vec![];
+    for frame in frames {
+        match calls.last_mut() {
+            Some((last, times)) if *last == frame => *times += 1,
+            _ => calls.push((frame, 1)),
+        }
+    }
+    let mut lines: Vec<String> = calls
+        .into_iter()
+        .map(|(frame, times)| match times {
+            1 => frame.to_string(),
+            times => format!("{} ({} vezes)", frame, times),
+        })
+        .collect();
+    if lines.len() > TRACE_LENGTH {
+        let hidden = lines.len() - TRACE_LENGTH;
+        let start = TRACE_LENGTH / 2;
+        lines.splice(
+            start..start + hidden,
+            vec![format!("... mais {} chamadas", hidden)],
+        );
+    }
+    lines
+}
+
+fn missing(args: &[String], one: &str, many: &str, and: &str) -> String {
+    let args: Vec<String> = args.iter().map(|arg| format!("\"{}\"", arg)).collect();
+    match args.split_last() {
+        Some((last, [])) => format!("{} {}", one, last),
+        Some((last, rest)) => format!("{} {} {} {}", many, rest.join(", "), and, last),
+        None => many.into(),
+    }
+}
+
+fn returned(source: &VoidSource, named: &str, nothing: &str, unknown: &str) -> String {
+    match source {
+        VoidSource::Value => String::new(),
+        VoidSource::Variable => format!(", {}", unknown),
+        VoidSource::Call(function) => format!(", {} {} {}", named, function, nothing),
+    }
+}
+
+fn condition(source: &Option<String>, within: &str) -> String {
+    match source {
+        Some(source) => format!(" {} \"{}\"", within, source),
+        None => String::new(),
+    }
+}
+
+fn details(values: &[(String, String)], message: &Option<String>, with: &str) -> String {
+    let values: Vec<String> = values
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    let mut details = String::new();
+    if !values.is_empty() {
+        details.push_str(&format!(" {} {}", with, values.join(", ")));
+    }
+    if let Some(message) = message {
+        details.push_str(&format!(": {}", message));
+    }
+    details
+}
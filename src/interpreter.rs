@@ -1,40 +1,209 @@
-use std::{cell::RefCell, collections::HashMap, convert::TryFrom, io};
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fs,
+    io::{self, IsTerminal, Write},
+    ops::{Add, Div, Mul, Sub},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
 
-use crate::{AstNode, Error, Expression, InputType, InterpreterError, TypeError, Value};
+use crate::{
+    is_constant_name, list::compare, load, AstNode, Error, Expression, Frame, InputType,
+    InterpreterError, Pattern, Rng, Type, TypeError, Value, VoidSource,
+};
+
+/// Stack left before a call grows the stack with a new segment on the heap
+const RED_ZONE: usize = 256 * 1024;
+/// Size of each stack segment allocated on the heap
+const STACK_SEGMENT: usize = 2 * 1024 * 1024;
+
+thread_local! {
+    /// Memory in bytes of the stack segments allocated on the heap
+    static STACK_MEMORY: Cell<usize> = const { Cell::new(0) };
+}
+
+fn stack_is_low() -> bool {
+    stacker::remaining_stack().is_none_or(|remaining| remaining < RED_ZONE)
+}
+
+/// Runs code that goes deeper as the program nests, growing the stack on the heap when it runs low
+pub fn nested<T>(f: impl FnOnce() -> T) -> T {
+    if !stack_is_low() {
+        return f();
+    }
+    STACK_MEMORY.set(STACK_MEMORY.get() + STACK_SEGMENT);
+    let result = stacker::grow(STACK_SEGMENT, f);
+    STACK_MEMORY.set(STACK_MEMORY.get() - STACK_SEGMENT);
+    result
+}
+
+type CallValues = (Vec<Value>, Vec<(String, Value)>);
 
 pub enum Return {
     None,
     Value(Value),
+    /// A `retorne` of a function call, run by the caller in place of the current call
+    TailCall(String, Vec<Value>, Vec<(String, Value)>, usize),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Function {
     pub args: Vec<String>,
+    pub defaults: Vec<Option<Expression>>,
     pub block: Vec<Box<AstNode>>,
+    pub captured: HashMap<String, Value>,
+    pub captured_functions: HashMap<String, Rc<Function>>,
 }
 
 impl Function {
-    pub fn new(args: Vec<String>, block: Vec<Box<AstNode>>) -> Self {
-        Self { args, block }
+    pub fn new(
+        args: Vec<String>,
+        defaults: Vec<Option<Expression>>,
+        block: Vec<Box<AstNode>>,
+    ) -> Self {
+        Self {
+            args,
+            defaults,
+            block,
+            captured: HashMap::new(),
+            captured_functions: HashMap::new(),
+        }
+    }
+
+    pub fn closure(
+        args: Vec<String>,
+        block: Vec<Box<AstNode>>,
+        captured: HashMap<String, Value>,
+        captured_functions: HashMap<String, Rc<Function>>,
+    ) -> Self {
+        Self {
+            defaults: vec![None; args.len()],
+            args,
+            block,
+            captured,
+            captured_functions,
+        }
+    }
+
+    /// Matches the given values with the arguments, by position and then by name
+    pub fn bind(
+        &self,
+        ident: &str,
+        variables: Vec<Value>,
+        named: Vec<(String, Value)>,
+    ) -> Result<HashMap<String, Value>, Error> {
+        if variables.len() > self.args.len() {
+            return Err(InterpreterError::WrongNumberOfArgs(
+                ident.into(),
+                self.args.len(),
+                variables.len(),
+                {
+                    if variables.len() == 1 {
+                        "was"
+                    } else {
+                        "were"
+                    }
+                }
+                .to_string(),
+            )
+            .into());
+        }
+
+        let mut args: HashMap<String, Value> = self.args.iter().cloned().zip(variables).collect();
+        for (arg, value) in named {
+            if !self.args.contains(&arg) {
+                return Err(InterpreterError::UnknownArgument(ident.into(), arg).into());
+            } else if args.contains_key(&arg) {
+                return Err(InterpreterError::RepeatedArgument(ident.into(), arg).into());
+            }
+            args.insert(arg, value);
+        }
+        let missing: Vec<String> = self
+            .args
+            .iter()
+            .zip(&self.defaults)
+            .filter(|(arg, default)| default.is_none() && !args.contains_key(*arg))
+            .map(|(arg, _)| arg.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(InterpreterError::MissingArguments(ident.into(), missing).into());
+        }
+        Ok(args)
+    }
+}
+
+impl PartialOrd for Function {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else {
+            None
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct Global {
     pub recursion: usize,
-    pub recursion_limit: usize,
+    /// Memory in bytes the stack segments can take before function calls stop
+    pub memory_limit: usize,
+    /// Depth of function calls allowed
+    pub depth_limit: Option<usize>,
+    pub steps: usize,
+    pub step_limit: Option<usize>,
+    pub started: Instant,
+    pub time_limit: Option<Duration>,
+    pub rng: Rng,
+    pub uppercase_constants: bool,
+    /// Lets conditions use values other than `verdadeiro` and `falso`, see `Value::truthy`
+    pub truthiness: bool,
+    pub files: Vec<PathBuf>,
 }
 
 impl Default for Global {
     fn default() -> Self {
         Self {
             recursion: 0,
-            #[cfg(not(debug_assertions))]
-            recursion_limit: 4000,
-            #[cfg(debug_assertions)]
-            recursion_limit: 200,
+            memory_limit: 64 * 1024 * 1024,
+            depth_limit: None,
+            steps: 0,
+            step_limit: None,
+            started: Instant::now(),
+            time_limit: None,
+            rng: Rng::default(),
+            uppercase_constants: false,
+            truthiness: false,
+            files: vec![],
+        }
+    }
+}
+
+impl Global {
+    /// Reads a value as a condition, following `--condicoes-flexiveis`
+    pub fn truth(&self, value: Value) -> Result<bool, Error> {
+        if self.truthiness {
+            return Ok(value.truthy());
+        }
+        bool::try_from(value)
+    }
+
+    /// Counts a statement, failing when the step or time limits are reached
+    pub fn step(&mut self) -> Result<(), Error> {
+        self.steps += 1;
+        match (self.step_limit, self.time_limit) {
+            (Some(limit), _) if self.steps > limit => {
+                Err(InterpreterError::StepLimit(limit).into())
+            }
+            (_, Some(limit)) if self.started.elapsed() > limit => {
+                Err(InterpreterError::TimeLimit(limit.as_secs_f64()).into())
+            }
+            _ => Ok(()),
         }
     }
 }
@@ -44,19 +213,26 @@ impl TryFrom<Value> for bool {
 
     fn try_from(value: Value) -> Result<Self, Error> {
         match value {
-            Value::Void => Err(TypeError::ToBoolError("Void".into()).into()),
-            Value::String(_) => Err(TypeError::ToBoolError("String".into()).into()),
-            Value::Integer(_) => Err(TypeError::ToBoolError("Integer".into()).into()),
-            Value::Float(_) => Err(TypeError::ToBoolError("Float".into()).into()),
+            Value::Void => Err(TypeError::ToBoolError("Void".into(), None).into()),
+            Value::String(_) => Err(TypeError::ToBoolError("String".into(), None).into()),
+            Value::Integer(_) => Err(TypeError::ToBoolError("Integer".into(), None).into()),
+            Value::Float(_) => Err(TypeError::ToBoolError("Float".into(), None).into()),
             Value::Bool(boolean) => Ok(boolean),
+            Value::List(_) => Err(TypeError::ToBoolError("List".into(), None).into()),
+            Value::Dictionary(_) => Err(TypeError::ToBoolError("Dictionary".into(), None).into()),
+            Value::Tuple(_) => Err(TypeError::ToBoolError("Tuple".into(), None).into()),
+            Value::Record(name, _) => Err(TypeError::ToBoolError(name, None).into()),
+            Value::Function(_) => Err(TypeError::ToBoolError("Function".into(), None).into()),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Scope<'a> {
     pub variables: RefCell<HashMap<String, Value>>,
-    pub functions: RefCell<HashMap<String, Function>>,
+    pub functions: RefCell<HashMap<String, Rc<Function>>>,
+    pub records: RefCell<HashMap<String, Vec<String>>>,
+    pub constants: RefCell<HashSet<String>>,
     pub parent: Option<&'a Scope<'a>>,
 }
 
@@ -65,6 +241,8 @@ impl<'a> Scope<'a> {
         Self {
             variables: RefCell::new(HashMap::new()),
             functions: RefCell::new(HashMap::new()),
+            records: RefCell::new(HashMap::new()),
+            constants: RefCell::new(HashSet::new()),
             parent: None,
         }
     }
@@ -73,15 +251,80 @@ impl<'a> Scope<'a> {
         Self {
             variables: RefCell::new(vars),
             functions: RefCell::new(HashMap::new()),
+            records: RefCell::new(HashMap::new()),
+            constants: RefCell::new(HashSet::new()),
             parent: Some(self),
         }
     }
 
     pub fn get_var(&self, ident: String) -> Option<Value> {
         let variables = self.variables.borrow();
-        match variables.get(&ident) {
-            Some(variable) => Some(variable.to_owned()),
-            None => None,
+        variables.get(&ident).map(|variable| variable.to_owned())
+    }
+
+    pub fn set_var(
+        &self,
+        ident: String,
+        value: Value,
+        constant: bool,
+        global: &Global,
+    ) -> Result<(), Error> {
+        if self.is_constant(&ident, global) {
+            return Err(InterpreterError::ConstantReassignment(ident).into());
+        }
+        if constant {
+            self.constants.borrow_mut().insert(ident.clone());
+        }
+        self.variables.borrow_mut().insert(ident, value);
+        Ok(())
+    }
+
+    fn is_constant(&self, ident: &str, global: &Global) -> bool {
+        self.constants.borrow().contains(ident)
+            || (global.uppercase_constants
+                && is_constant_name(ident)
+                && self.variables.borrow().contains_key(ident))
+    }
+
+    /// Changes part of a variable in place, like a key of a dictionary or a field of a record
+    pub fn change_var(
+        &self,
+        ident: String,
+        global: &Global,
+        change: impl FnOnce(&mut Value) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        if self.is_constant(&ident, global) {
+            return Err(InterpreterError::ConstantReassignment(ident).into());
+        }
+        match self.variables.borrow_mut().get_mut(&ident) {
+            Some(value) => change(value),
+            None => Err(InterpreterError::UndefinedVariable(ident).into()),
+        }
+    }
+
+    pub fn define(
+        &self,
+        ident: String,
+        expr: Expression,
+        kind: Option<Type>,
+        constant: bool,
+        global: &mut Global,
+    ) -> Result<(), Error> {
+        let value = self.interpret_expr(expr, global)?;
+        if let Some(kind) = kind {
+            if !kind.matches(&value) {
+                return Err(
+                    TypeError::VariableType(ident, kind.name(), value.type_name().into()).into(),
+                );
+            }
+        }
+        self.set_var(ident, value, constant, global)
+    }
+
+    pub fn get_record(&self, ident: &str) -> Option<Vec<String>> {
+        match self.records.borrow().get(ident) {
+            Some(fields) => Some(fields.clone()),
+            None => self.parent.and_then(|parent| parent.get_record(ident)),
         }
     }
 
@@ -90,12 +333,27 @@ impl<'a> Scope<'a> {
         program: Vec<Box<AstNode>>,
         global: &mut Global,
     ) -> Result<Return, Error> {
-        let program = program.into_iter();
+        for step in program.iter() {
+            if let AstNode::Function {
+                ident,
+                args,
+                defaults,
+                block,
+                ..
+            } = step.as_ref()
+            {
+                self.functions.borrow_mut().insert(
+                    ident.clone(),
+                    Rc::new(Function::new(args.clone(), defaults.clone(), block.clone())),
+                );
+            }
+        }
 
         for step in program {
+            global.step()?;
             match self.interpret_ast(*step, global)? {
                 Return::None => (),
-                Return::Value(value) => return Ok(Return::Value(value)),
+                value => return Ok(value),
             }
         }
         Ok(Return::None)
@@ -105,62 +363,197 @@ impl<'a> Scope<'a> {
         &self,
         ident: String,
         variables: Vec<Value>,
+        named: Vec<(String, Value)>,
+        line: Option<usize>,
         global: &mut Global,
     ) -> Result<Value, Error> {
-        if global.recursion > global.recursion_limit {
-            let peak = global.recursion.clone();
-            global.recursion = 0;
-            return Err(InterpreterError::RecursionLimit(peak - 1).into());
+        match self.lookup_fn(&ident) {
+            Some((parent, function)) => {
+                parent.call_fn(ident, function, variables, named, line, global)
+            }
+            None => Err(InterpreterError::UndefinedFunction(ident).into()),
         }
-        global.recursion += 1;
+    }
+
+    /// Finds a function and the scope it should be called from, a variable
+    /// holding a function hiding the ones defined in outer scopes
+    pub fn lookup_fn(&self, ident: &str) -> Option<(&Scope<'a>, Rc<Function>)> {
+        if self.functions.borrow().contains_key(ident) {
+            return self.find_fn(ident);
+        }
+        match self.get_var(ident.into()) {
+            Some(Value::Function(function)) => Some((self, function)),
+            _ => self.find_fn(ident),
+        }
+    }
 
+    /// Finds a defined function and the scope it was defined in
+    pub fn find_fn(&self, ident: &str) -> Option<(&Scope<'a>, Rc<Function>)> {
         let mut me = self;
         loop {
-            let block = match me.functions.borrow_mut().get_mut(&ident) {
-                None => None,
-                Some(func) => {
-                    if variables.len() != func.args.len() {
-                        //global.recursion = 0;
-                        return Err(InterpreterError::WrongNumberOfArgs(
-                            ident,
-                            func.args.len(),
-                            variables.len(),
-                            {
-                                if variables.len() == 1 {
-                                    "was"
-                                } else {
-                                    "were"
-                                }
-                            }
-                            .to_string(),
-                        )
-                        .into());
-                    }
+            let function = me.functions.borrow().get(ident).cloned();
+            if let Some(function) = function {
+                return Some((me, function));
+            }
+            me = me.parent?;
+        }
+    }
 
-                    let mut vars = HashMap::new();
-                    for (i, arg) in variables.iter().enumerate() {
-                        vars.insert(func.args[i].clone(), arg.clone());
+    /// Runs a tail call that can't take the place of a function call, like one at the top level
+    pub fn finish(&self, result: Return, global: &mut Global) -> Result<Return, Error> {
+        match result {
+            Return::TailCall(ident, variables, named, line) => Ok(Return::Value(
+                self.interpret_fn(ident, variables, named, Some(line), global)?,
+            )),
+            result => Ok(result),
+        }
+    }
+
+    pub fn call_fn(
+        &self,
+        ident: String,
+        function: Rc<Function>,
+        variables: Vec<Value>,
+        named: Vec<(String, Value)>,
+        line: Option<usize>,
+        global: &mut Global,
+    ) -> Result<Value, Error> {
+        if global
+            .depth_limit
+            .is_some_and(|limit| global.recursion >= limit)
+        {
+            return Err(InterpreterError::RecursionLimit(global.recursion).into());
+        }
+        let needed = if stack_is_low() { STACK_SEGMENT } else { 0 };
+        if STACK_MEMORY.get() + needed > global.memory_limit {
+            return Err(InterpreterError::MemoryLimit(global.memory_limit / (1024 * 1024)).into());
+        }
+        global.recursion += 1;
+        let result = nested(|| self.run_fn(ident, function, variables, named, line, global));
+        global.recursion -= 1;
+        result
+    }
+
+    /// Runs a function, running the tail calls it returns in the same frame
+    fn run_fn(
+        &self,
+        mut ident: String,
+        mut function: Rc<Function>,
+        mut variables: Vec<Value>,
+        mut named: Vec<(String, Value)>,
+        mut line: Option<usize>,
+        global: &mut Global,
+    ) -> Result<Value, Error> {
+        let mut me = self;
+        let mut tail_calls = 0;
+        loop {
+            let args = function.bind(&ident, variables, named)?;
+            let mut vars = function.captured.clone();
+            vars.extend(args.clone());
+            let scope = me.go_down(vars);
+            scope
+                .functions
+                .borrow_mut()
+                .extend(function.captured_functions.clone());
+            let frame = |error: Error| {
+                error.called(Frame {
+                    function: ident.clone(),
+                    line,
+                    args: function
+                        .args
+                        .iter()
+                        .filter_map(|arg| args.get(arg))
+                        .map(|value| value.to_string())
+                        .collect(),
+                    tail_calls,
+                })
+            };
+            let result = scope
+                .default_args(&function, &args, global)
+                .and_then(|_| scope.interpret_program(function.block.clone(), global));
+            match result.map_err(&frame)? {
+                Return::None => return Ok(Value::Void),
+                Return::Value(val) => return Ok(val),
+                Return::TailCall(next, next_variables, next_named, next_line) => {
+                    let local = scope.functions.borrow().contains_key(&next)
+                        || matches!(scope.get_var(next.clone()), Some(Value::Function(_)));
+                    let target = if local { None } else { me.find_fn(&next) };
+                    match target {
+                        Some((parent, target)) => {
+                            me = parent;
+                            ident = next;
+                            function = target;
+                            variables = next_variables;
+                            named = next_named;
+                            line = Some(next_line);
+                            tail_calls += 1;
+                        }
+                        None => {
+                            return scope
+                                .interpret_fn(
+                                    next,
+                                    next_variables,
+                                    next_named,
+                                    Some(next_line),
+                                    global,
+                                )
+                                .map_err(&frame)
+                        }
                     }
-                    Some((func.block.clone(), vars))
                 }
-            };
-            if let Some((block, vars)) = block {
-                let scope = me.go_down(vars);
-                let val = match scope.interpret_program(block, global)? {
-                    Return::None => Value::Void,
-                    Return::Value(val) => val,
-                };
-                global.recursion -= 1;
-                break Ok(val);
-            } else if let Some(parent) = &me.parent {
-                me = &parent;
-            } else {
-                global.recursion = 0;
-                return Err(InterpreterError::UndefinedFunction(ident).into());
             }
         }
     }
 
+    pub fn interpret_args(
+        &self,
+        vars: Vec<Expression>,
+        named: Vec<(String, Expression)>,
+        global: &mut Global,
+    ) -> Result<CallValues, Error> {
+        let mut variables = vec![];
+        for var in vars {
+            variables.push(self.interpret_expr(var, global)?);
+        }
+        let mut values = vec![];
+        for (arg, var) in named {
+            values.push((arg, self.interpret_expr(var, global)?));
+        }
+        Ok((variables, values))
+    }
+
+    fn default_args(
+        &self,
+        function: &Function,
+        args: &HashMap<String, Value>,
+        global: &mut Global,
+    ) -> Result<(), Error> {
+        for (arg, default) in function.args.iter().zip(&function.defaults) {
+            if let (Some(default), false) = (default, args.contains_key(arg)) {
+                let value = self.interpret_expr(default.clone(), global)?;
+                self.variables.borrow_mut().insert(arg.clone(), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// The functions that can be called from this scope, for closures to keep
+    pub fn visible_fns(&self) -> HashMap<String, Rc<Function>> {
+        let mut functions = match self.parent {
+            Some(parent) => parent.visible_fns(),
+            None => HashMap::new(),
+        };
+        functions.extend(self.functions.borrow().clone());
+        functions
+    }
+
+    pub fn get_fn(&self, ident: &str) -> Option<Rc<Function>> {
+        match self.functions.borrow().get(ident) {
+            Some(function) => Some(function.clone()),
+            None => self.parent.and_then(|parent| parent.get_fn(ident)),
+        }
+    }
+
     pub fn interpret_expr(&self, expr: Expression, global: &mut Global) -> Result<Value, Error> {
         macro_rules! interpret_operation {
             ($left:expr, $right:expr, $op:tt) => {
@@ -172,56 +565,345 @@ impl<'a> Scope<'a> {
         macro_rules! interpret_bool {
             ($left:expr, $right:expr, $op:tt) => {
 
-                bool::try_from(self.interpret_expr($left, global)?)? $op bool::try_from(self.interpret_expr($right, global)?)?
+                self.condition($left, None, global)? $op self.condition($right, None, global)?
             }
         }
 
         match expr {
             Expression::Variable(ident) => match self.get_var(ident.clone()) {
                 Some(value) => Ok(value),
-                None => Err(InterpreterError::UndefinedVariable(ident).into()),
+                None => match self.get_fn(&ident) {
+                    Some(function) => Ok(Value::Function(function)),
+                    None => Err(InterpreterError::UndefinedVariable(ident).into()),
+                },
             },
+            Expression::AnonymousFunction(args, block) => {
+                Ok(Value::Function(Rc::new(Function::closure(
+                    args,
+                    block,
+                    self.variables.borrow().clone(),
+                    self.visible_fns(),
+                ))))
+            }
             Expression::Value(value) => Ok(value),
-            Expression::Sum(left, right) => Ok(interpret_operation!(*left, *right, +)?),
-            Expression::Sub(left, right) => Ok(interpret_operation!(*left, *right, -)?),
-            Expression::Mult(left, right) => Ok(interpret_operation!(*left, *right, *)?),
-            Expression::Div(left, right) => Ok(interpret_operation!(*left, *right, /)?),
+            Expression::Sum(left, right) => self.arithmetic(*left, *right, Add::add, global),
+            Expression::Sub(left, right) => self.arithmetic(*left, *right, Sub::sub, global),
+            Expression::Mult(left, right) => self.arithmetic(*left, *right, Mul::mul, global),
+            Expression::Div(left, right) => self.arithmetic(*left, *right, Div::div, global),
             Expression::Is(left, right) => Ok(interpret_operation!(*left, *right, ==).into()),
             Expression::IsNot(left, right) => Ok(interpret_operation!(*left, *right, !=).into()),
-            Expression::Smlr(left, right) => Ok(interpret_operation!(*left, *right, <).into()),
-            Expression::Bigr(left, right) => Ok(interpret_operation!(*left, *right, >).into()),
-            Expression::SmlrEq(left, right) => Ok(interpret_operation!(*left, *right, <=).into()),
-            Expression::BigrEq(left, right) => Ok(interpret_operation!(*left, *right, >=).into()),
+            Expression::Smlr(left, right) => {
+                self.comparison(*left, *right, Ordering::is_lt, global)
+            }
+            Expression::Bigr(left, right) => {
+                self.comparison(*left, *right, Ordering::is_gt, global)
+            }
+            Expression::SmlrEq(left, right) => {
+                self.comparison(*left, *right, Ordering::is_le, global)
+            }
+            Expression::BigrEq(left, right) => {
+                self.comparison(*left, *right, Ordering::is_ge, global)
+            }
             Expression::And(left, right) => Ok(interpret_bool!(*left, *right, &&).into()),
             Expression::Or(left, right) => Ok(interpret_bool!(*left, *right, ||).into()),
-            Expression::FnCall(ident, vars) => self.interpret_fn(
-                ident,
-                vars.into_iter()
-                    .map(|var| -> Value { self.interpret_expr(var, global).unwrap() })
-                    .collect(),
-                global,
-            ),
-            Expression::Entrada(input_type) => {
-                let mut input = String::new();
-                match io::stdin().read_line(&mut input) {
-                    Ok(_) => match input_type {
-                        InputType::Number => match input.trim().parse::<f32>() {
-                            Ok(number) => Ok(Value::Float(number)),
-                            Err(_) => Err(InterpreterError::ParseError(
-                                input.trim().to_string(),
-                                "float".to_string(),
-                            )
-                            .into()),
-                        },
-                        InputType::String => Ok(Value::String(input.trim().to_string())),
-                    },
-                    Err(_) => Err(InterpreterError::InputError.into()),
+            Expression::FnCall(ident, vars, named, line) => {
+                let (variables, named) = self.interpret_args(vars, named, global)?;
+                self.interpret_fn(ident, variables, named, Some(line), global)
+            }
+            Expression::Math(function, args) => {
+                let mut values = vec![];
+                for arg in args {
+                    values.push(self.interpret_expr(arg, global)?);
+                }
+                function.apply(values)
+            }
+            Expression::Tuple(exprs) => {
+                let mut values = vec![];
+                for expr in exprs {
+                    values.push(self.interpret_expr(expr, global)?);
+                }
+                Ok(Value::Tuple(values))
+            }
+            Expression::ListOp(function, args) => {
+                let mut values = vec![];
+                for arg in args {
+                    values.push(self.interpret_expr(arg, global)?);
+                }
+                self.apply_list_fn(function, values, global)
+            }
+            Expression::List(exprs) => {
+                let mut list = vec![];
+                for expr in exprs {
+                    list.push(self.interpret_expr(expr, global)?);
+                }
+                Ok(Value::List(list))
+            }
+            Expression::RandomNumber(low, high) => {
+                let low = self.interpret_expr(*low, global)?;
+                let high = self.interpret_expr(*high, global)?;
+                global.rng.number_between(low, high)
+            }
+            Expression::RandomItem(list) => {
+                let list = self.interpret_expr(*list, global)?;
+                global.rng.item_of(list)
+            }
+            Expression::Dictionary(entries) => {
+                let mut dictionary = Value::Dictionary(vec![]);
+                for (key, value) in entries {
+                    let key = self.interpret_expr(key, global)?;
+                    let value = self.interpret_expr(value, global)?;
+                    dictionary.insert_key(key, value)?;
+                }
+                Ok(dictionary)
+            }
+            Expression::Lookup(ident, key) => {
+                let key = self.interpret_expr(*key, global)?;
+                match self.get_var(ident.clone()) {
+                    Some(dictionary) => dictionary.get_key(&key),
+                    None => Err(InterpreterError::UndefinedVariable(ident).into()),
+                }
+            }
+            Expression::HasKey(ident, key) => {
+                let key = self.interpret_expr(*key, global)?;
+                match self.get_var(ident.clone()) {
+                    Some(dictionary) => Ok(dictionary.has_key(&key)?.into()),
+                    None => Err(InterpreterError::UndefinedVariable(ident).into()),
+                }
+            }
+            Expression::NewRecord(ident, exprs) => {
+                let fields = match self.get_record(&ident) {
+                    Some(fields) => fields,
+                    None => return Err(InterpreterError::UndefinedRecord(ident).into()),
+                };
+                if fields.len() != exprs.len() {
+                    return Err(InterpreterError::WrongNumberOfFields(
+                        ident,
+                        fields.len(),
+                        exprs.len(),
+                        {
+                            if exprs.len() == 1 {
+                                "was"
+                            } else {
+                                "were"
+                            }
+                        }
+                        .to_string(),
+                    )
+                    .into());
+                }
+                let mut values = vec![];
+                for (field, expr) in fields.into_iter().zip(exprs) {
+                    values.push((field, self.interpret_expr(expr, global)?));
                 }
+                Ok(Value::Record(ident, values))
+            }
+            Expression::Field(field, record) => {
+                self.interpret_expr(*record, global)?.get_field(&field)
+            }
+            Expression::Convert(expr, to) => self.interpret_expr(*expr, global)?.convert(to),
+            Expression::TypeOf(expr) => Ok(Value::String(
+                self.interpret_expr(*expr, global)?
+                    .ptbr_type_name()
+                    .to_string(),
+            )),
+            Expression::Entrada(input_type, prompt) => {
+                let prompt = match prompt {
+                    Some(prompt) => Some(self.interpret_expr(*prompt, global)?),
+                    None => None,
+                };
+                self.read_input(input_type, prompt)
+            }
+        }
+    }
+
+    /// Runs an arithmetic operation, naming the function that returned `nada` when there is one
+    fn arithmetic(
+        &self,
+        left: Expression,
+        right: Expression,
+        operation: fn(Value, Value) -> Result<Value, Error>,
+        global: &mut Global,
+    ) -> Result<Value, Error> {
+        let source = |expr: &Expression| match expr {
+            Expression::FnCall(ident, ..) => VoidSource::Call(ident.clone()),
+            Expression::Variable(_) => VoidSource::Variable,
+            _ => VoidSource::Value,
+        };
+        let (left_source, right_source) = (source(&left), source(&right));
+        let left = self.interpret_expr(left, global)?;
+        let right = self.interpret_expr(right, global)?;
+        let source = match (&left, &right) {
+            (Value::Void, _) => left_source,
+            (_, Value::Void) => right_source,
+            _ => VoidSource::Value,
+        };
+        operation(left, right).map_err(|error| match error {
+            Error::TypeError(TypeError::VoidOperation(operation, VoidSource::Value)) => {
+                TypeError::VoidOperation(operation, source).into()
+            }
+            error => error,
+        })
+    }
+
+    fn comparison(
+        &self,
+        left: Expression,
+        right: Expression,
+        wanted: fn(Ordering) -> bool,
+        global: &mut Global,
+    ) -> Result<Value, Error> {
+        let left = self.interpret_expr(left, global)?;
+        let right = self.interpret_expr(right, global)?;
+        Ok(wanted(compare(&left, &right)?).into())
+    }
+
+    pub fn read_input(&self, input_type: InputType, prompt: Option<Value>) -> Result<Value, Error> {
+        let interactive = io::stdin().is_terminal();
+        loop {
+            if let Some(prompt) = &prompt {
+                print!("{} ", prompt);
+                io::stdout()
+                    .flush()
+                    .map_err(|_| Error::from(InterpreterError::InputError))?;
+            }
+            let mut input = String::new();
+            match io::stdin().read_line(&mut input) {
+                Ok(0) => return Err(InterpreterError::EndOfInput.into()),
+                Ok(_) => (),
+                Err(_) => return Err(InterpreterError::InputError.into()),
+            }
+            match input_type.parse(&input) {
+                Ok(value) => return Ok(value),
+                Err(error) if interactive => eprintln!("{}", error),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    pub fn import(&self, path: String, variables: bool, global: &mut Global) -> Result<(), Error> {
+        let file = match global.files.last().and_then(|file| file.parent()) {
+            Some(dir) => dir.join(&path),
+            None => Path::new(&path).to_path_buf(),
+        };
+        let file = match fs::canonicalize(&file) {
+            Ok(file) => file,
+            Err(error) => return Err(InterpreterError::ImportError(path, error.kind()).into()),
+        };
+        if global.files.contains(&file) {
+            return Err(InterpreterError::CyclicImport(path).into());
+        }
+        let program = match fs::read(&file) {
+            Ok(program) => String::from_utf8_lossy(&program).to_string(),
+            Err(error) => return Err(InterpreterError::ImportError(path, error.kind()).into()),
+        };
+
+        global.files.push(file);
+        let scope = Scope::new();
+        let result = load(&program, global)
+            .and_then(|ast| scope.interpret_program(ast, global))
+            .and_then(|result| scope.finish(result, global));
+        global.files.pop();
+        if let Err(error) = result {
+            return Err(InterpreterError::ImportedFile(path, Box::new(error)).into());
+        }
+
+        self.functions.borrow_mut().extend(scope.functions.take());
+        self.records.borrow_mut().extend(scope.records.take());
+        if variables {
+            let constants = scope.constants.take();
+            for (ident, value) in scope.variables.take() {
+                let constant = constants.contains(&ident);
+                self.set_var(ident, value, constant, global)?;
             }
         }
+        Ok(())
+    }
+
+    #[inline(never)]
+    pub fn interpret_match(
+        &self,
+        expr: Expression,
+        cases: Vec<(Vec<Pattern>, Vec<Box<AstNode>>)>,
+        default: Option<Vec<Box<AstNode>>>,
+        global: &mut Global,
+    ) -> Result<Return, Error> {
+        let subject = self.interpret_expr(expr, global)?;
+        for (patterns, block) in cases {
+            for pattern in patterns {
+                let matched = match pattern {
+                    Pattern::Value(value) => bool::try_from(self.interpret_expr(
+                        Expression::Is(
+                            Box::new(Expression::Value(subject.clone())),
+                            Box::new(value),
+                        ),
+                        global,
+                    )?)?,
+                    Pattern::Range(start, end) => {
+                        let start = self.interpret_expr(start, global)?;
+                        let end = self.interpret_expr(end, global)?;
+                        compare(&subject, &start)?.is_ge() && compare(&subject, &end)?.is_le()
+                    }
+                };
+                if matched {
+                    return self.interpret_program(block, global);
+                }
+            }
+        }
+        match default {
+            Some(block) => self.interpret_program(block, global),
+            None => Ok(Return::None),
+        }
+    }
+
+    #[inline(never)]
+    fn interpret_assert(
+        &self,
+        comp: Expression,
+        source: String,
+        message: Option<Expression>,
+        global: &mut Global,
+    ) -> Result<(), Error> {
+        if self.condition(comp.clone(), Some(source.clone()), global)? {
+            return Ok(());
+        }
+        let mut names = vec![];
+        comp.variables(&mut names);
+        let values = names
+            .into_iter()
+            .filter_map(|name| match self.get_var(name.clone()) {
+                Some(Value::String(string)) => Some((name, format!("\"{}\"", string))),
+                Some(value) => Some((name, value.to_string())),
+                None => None,
+            })
+            .collect();
+        let message = match message {
+            Some(message) => Some(self.interpret_expr(message, global)?.to_string()),
+            None => None,
+        };
+        Err(InterpreterError::AssertionFailed(source, values, message).into())
+    }
+
+    /// Evaluates a condition, which must be `verdadeiro` or `falso` unless truthiness is enabled
+    pub fn condition(
+        &self,
+        comp: Expression,
+        source: Option<String>,
+        global: &mut Global,
+    ) -> Result<bool, Error> {
+        let value = self.interpret_expr(comp, global)?;
+        match global.truth(value) {
+            Err(Error::TypeError(TypeError::ToBoolError(kind, _))) => {
+                Err(TypeError::ToBoolError(kind, source).into())
+            }
+            result => result,
+        }
     }
 
     pub fn interpret_ast(&self, ast: AstNode, global: &mut Global) -> Result<Return, Error> {
+        nested(|| self.interpret_statement(ast, global))
+    }
+
+    fn interpret_statement(&self, ast: AstNode, global: &mut Global) -> Result<Return, Error> {
         match ast {
             AstNode::Print(exprs) => {
                 let mut print_string = String::new();
@@ -233,42 +915,179 @@ impl<'a> Scope<'a> {
                 println!("{}", print_string.trim());
             }
             AstNode::Val(_) => {}
-            AstNode::Definition { ident, expr } => {
-                let value = self.interpret_expr(expr, global)?;
-                {
-                    self.variables.borrow_mut().insert(ident, value);
+            AstNode::Definition { ident, expr, kind } => {
+                self.define(ident, expr, kind, false, global)?
+            }
+            AstNode::Destructure { idents, expr } => {
+                let values = match self.interpret_expr(expr, global)? {
+                    Value::Tuple(values) => values,
+                    value => vec![value],
+                };
+                if values.len() != idents.len() {
+                    return Err(
+                        InterpreterError::WrongNumberOfValues(idents.len(), values.len()).into(),
+                    );
+                }
+                for (ident, value) in idents.into_iter().zip(values) {
+                    self.set_var(ident, value, false, global)?;
                 }
             }
-            AstNode::If { comp, block, senao } => {
-                if let Value::Bool(boolean) = self.interpret_expr(comp, global)? {
-                    if boolean {
-                        return self.interpret_program(block, global);
-                    } else if let Some(block) = senao {
-                        return self.interpret_program(block, global);
-                    }
+            AstNode::Constant { ident, expr, kind } => {
+                self.define(ident, expr, kind, true, global)?
+            }
+            AstNode::If {
+                comp,
+                source,
+                block,
+                senao,
+            } => {
+                if self.condition(comp, Some(source), global)? {
+                    return self.interpret_program(block, global);
+                } else if let Some(block) = senao {
+                    return self.interpret_program(block, global);
                 }
             }
-            AstNode::While { comp, block } => {
-                while let Value::Bool(true) = self.interpret_expr(comp.clone(), global)? {
-                    self.interpret_program(block.clone(), global)?;
+            AstNode::While {
+                comp,
+                source,
+                block,
+            } => {
+                while self.condition(comp.clone(), Some(source.clone()), global)? {
+                    match self.interpret_program(block.clone(), global)? {
+                        Return::None => {}
+                        result => return Ok(result),
+                    }
                 }
             }
-            AstNode::Function { ident, args, block } => {
+            AstNode::Function {
+                ident,
+                args,
+                defaults,
+                block,
+                ..
+            } => {
                 self.functions
                     .borrow_mut()
-                    .insert(ident, Function::new(args, block));
+                    .insert(ident, Rc::new(Function::new(args, defaults, block)));
             }
-            AstNode::FnCall { ident, vars } => {
-                self.interpret_fn(
-                    ident,
-                    vars.into_iter()
-                        .map(|expr| self.interpret_expr(expr, global).unwrap())
-                        .collect(),
-                    global,
-                )?;
+            AstNode::FnCall {
+                ident,
+                vars,
+                named,
+                line,
+            } => {
+                let (variables, named) = self.interpret_args(vars, named, global)?;
+                self.interpret_fn(ident, variables, named, Some(line), global)?;
+            }
+            AstNode::Return(Expression::FnCall(ident, vars, named, line)) => {
+                let (variables, named) = self.interpret_args(vars, named, global)?;
+                return Ok(Return::TailCall(ident, variables, named, line));
             }
             AstNode::Return(expr) => return Ok(Return::Value(self.interpret_expr(expr, global)?)),
             AstNode::Expression(_) => {}
+            AstNode::DefineKey { ident, key, expr } => {
+                let key = self.interpret_expr(key, global)?;
+                let value = self.interpret_expr(expr, global)?;
+                self.change_var(ident, global, |dictionary| {
+                    dictionary.insert_key(key, value)
+                })?
+            }
+            AstNode::RemoveKey { ident, key } => {
+                let key = self.interpret_expr(key, global)?;
+                self.change_var(ident, global, |dictionary| dictionary.remove_key(&key))?
+            }
+            AstNode::ForEach {
+                ident,
+                iterable,
+                block,
+            } => {
+                for item in self.interpret_expr(iterable, global)?.items()? {
+                    self.set_var(ident.clone(), item, false, global)?;
+                    match self.interpret_program(block.clone(), global)? {
+                        Return::None => {}
+                        result => return Ok(result),
+                    }
+                }
+            }
+            AstNode::Try {
+                block,
+                ident,
+                catch,
+            } => {
+                match self
+                    .interpret_program(block, global)
+                    .and_then(|result| self.finish(result, global))
+                {
+                    Ok(Return::None) => {}
+                    Ok(result) => return Ok(result),
+                    Err(error)
+                        if matches!(
+                            error.root(),
+                            Error::InterpreterError(
+                                InterpreterError::EndOfInput
+                                    | InterpreterError::MemoryLimit(_)
+                                    | InterpreterError::RecursionLimit(_)
+                                    | InterpreterError::StepLimit(_)
+                                    | InterpreterError::TimeLimit(_)
+                            )
+                        ) =>
+                    {
+                        return Err(error)
+                    }
+                    Err(error) => {
+                        if let Some(ident) = ident {
+                            self.set_var(
+                                ident,
+                                Value::Record(
+                                    "Erro".into(),
+                                    vec![
+                                        ("mensagem".into(), Value::String(error.mensagem())),
+                                        (
+                                            "categoria".into(),
+                                            Value::String(error.categoria().into()),
+                                        ),
+                                    ],
+                                ),
+                                false,
+                                global,
+                            )?;
+                        }
+                        return self.interpret_program(catch, global);
+                    }
+                }
+            }
+            AstNode::Match {
+                expr,
+                cases,
+                default,
+            } => return self.interpret_match(expr, cases, default, global),
+            AstNode::Import { path, variables } => self.import(path, variables, global)?,
+            AstNode::Assert {
+                comp,
+                source,
+                message,
+            } => self.interpret_assert(comp, source, message, global)?,
+            AstNode::Throw(expr) => {
+                return Err(InterpreterError::UserError(
+                    self.interpret_expr(expr, global)?.to_string(),
+                )
+                .into())
+            }
+            AstNode::Record { ident, fields } => {
+                self.records.borrow_mut().insert(ident, fields);
+            }
+            AstNode::DefineField { ident, field, expr } => {
+                let value = self.interpret_expr(expr, global)?;
+                self.change_var(ident, global, |record| record.set_field(&field, value))?
+            }
+            AstNode::Seed(expr) => match self.interpret_expr(expr, global)? {
+                Value::Integer(seed) => global.rng.seed(seed as u64),
+                value => {
+                    return Err(
+                        TypeError::NotANumber("seeding".into(), value.type_name().into()).into(),
+                    )
+                }
+            },
         }
         Ok(Return::None)
     }
@@ -0,0 +1,230 @@
+use std::cmp::Ordering;
+
+use crate::{Error, Global, InterpreterError, ListFn, Scope, TypeError, Value};
+
+impl ListFn {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ListFn::Map => "mapping",
+            ListFn::Filter => "filtering",
+            ListFn::Reduce => "reduction",
+            ListFn::Sort => "sorting",
+            ListFn::Any => "any test",
+            ListFn::All => "all test",
+            ListFn::Sum => "sum",
+            ListFn::Mean => "mean",
+            ListFn::Median => "median",
+            ListFn::Max => "maximum",
+            ListFn::Min => "minimum",
+        }
+    }
+
+    /// Keyword the function is written with, used to name it in stack traces
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            ListFn::Map => "mapear",
+            ListFn::Filter => "filtrar",
+            ListFn::Reduce => "reduzir",
+            ListFn::Sort => "ordenar",
+            ListFn::Any => "algum de",
+            ListFn::All => "todos de",
+            ListFn::Sum => "soma de",
+            ListFn::Mean => "média de",
+            ListFn::Median => "mediana de",
+            ListFn::Max => "maior de",
+            ListFn::Min => "menor de",
+        }
+    }
+}
+
+impl<'a> Scope<'a> {
+    pub fn apply_list_fn(
+        &self,
+        function: ListFn,
+        args: Vec<Value>,
+        global: &mut Global,
+    ) -> Result<Value, Error> {
+        let mut args = args.into_iter();
+        let list = match args.next() {
+            Some(Value::List(list)) => list,
+            Some(value) => {
+                return Err(
+                    TypeError::NotAList(function.name().into(), value.type_name().into()).into(),
+                )
+            }
+            None => vec![],
+        };
+        let call = |callback: &Value, values: Vec<Value>, global: &mut Global| match callback {
+            Value::Function(callback) => self.call_fn(
+                function.keyword().into(),
+                callback.clone(),
+                values,
+                vec![],
+                None,
+                global,
+            ),
+            value => Err(Error::from(TypeError::NotAFunction(
+                function.name().into(),
+                value.type_name().into(),
+            ))),
+        };
+        let callback = args.next().unwrap_or(Value::Void);
+
+        match function {
+            ListFn::Map => {
+                let mut result = vec![];
+                for item in list {
+                    result.push(call(&callback, vec![item], global)?);
+                }
+                Ok(Value::List(result))
+            }
+            ListFn::Filter => {
+                let mut result = vec![];
+                for item in list {
+                    let keep = call(&callback, vec![item.clone()], global)?;
+                    if global.truth(keep)? {
+                        result.push(item);
+                    }
+                }
+                Ok(Value::List(result))
+            }
+            ListFn::Any | ListFn::All => {
+                let wanted = function == ListFn::Any;
+                for item in list {
+                    let result = call(&callback, vec![item], global)?;
+                    if global.truth(result)? == wanted {
+                        return Ok(wanted.into());
+                    }
+                }
+                Ok((!wanted).into())
+            }
+            ListFn::Reduce => {
+                let mut list = list.into_iter();
+                let mut accumulator = match args.next() {
+                    Some(start) => start,
+                    None => match list.next() {
+                        Some(first) => first,
+                        None => {
+                            return Err(InterpreterError::EmptyList(function.name().into()).into())
+                        }
+                    },
+                };
+                for item in list {
+                    accumulator = call(&callback, vec![accumulator, item], global)?;
+                }
+                Ok(accumulator)
+            }
+            ListFn::Sort => {
+                let mut keys = vec![];
+                for item in list.iter() {
+                    match callback {
+                        Value::Void => keys.push(item.clone()),
+                        _ => keys.push(call(&callback, vec![item.clone()], global)?),
+                    }
+                }
+                let mut indexes: Vec<usize> = (0..list.len()).collect();
+                let mut error = None;
+                indexes.sort_by(|&left, &right| match compare(&keys[left], &keys[right]) {
+                    Ok(ordering) => ordering,
+                    Err(err) => {
+                        error.get_or_insert(err);
+                        Ordering::Equal
+                    }
+                });
+                match error {
+                    Some(error) => Err(error),
+                    None => Ok(Value::List(
+                        indexes
+                            .into_iter()
+                            .map(|index| list[index].clone())
+                            .collect(),
+                    )),
+                }
+            }
+            ListFn::Sum => {
+                let mut list = list.into_iter();
+                let mut sum = list.next().unwrap_or(Value::Integer(0));
+                for item in list {
+                    sum = (sum + item)?;
+                }
+                Ok(sum)
+            }
+            ListFn::Mean => {
+                if list.is_empty() {
+                    return Err(InterpreterError::EmptyList(function.name().into()).into());
+                }
+                let len = list.len() as f32;
+                let mut sum = 0.0;
+                for item in list.iter() {
+                    sum += number(function, item)?;
+                }
+                Ok(Value::Float(sum / len))
+            }
+            ListFn::Median => {
+                if list.is_empty() {
+                    return Err(InterpreterError::EmptyList(function.name().into()).into());
+                }
+                let mut numbers = vec![];
+                for item in list.iter() {
+                    numbers.push(number(function, item)?);
+                }
+                numbers.sort_by(|left, right| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+                let middle = numbers.len() / 2;
+                if numbers.len() % 2 == 1 {
+                    Ok(Value::Float(numbers[middle]))
+                } else {
+                    Ok(Value::Float((numbers[middle - 1] + numbers[middle]) / 2.0))
+                }
+            }
+            ListFn::Max | ListFn::Min => {
+                let mut list = list.into_iter();
+                let mut best = match list.next() {
+                    Some(first) => first,
+                    None => return Err(InterpreterError::EmptyList(function.name().into()).into()),
+                };
+                let wanted = match function {
+                    ListFn::Max => Ordering::Greater,
+                    _ => Ordering::Less,
+                };
+                for item in list {
+                    if compare(&item, &best)? == wanted {
+                        best = item;
+                    }
+                }
+                Ok(best)
+            }
+        }
+    }
+}
+
+fn number(function: ListFn, value: &Value) -> Result<f32, Error> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f32),
+        Value::Float(float) => Ok(*float),
+        value => {
+            Err(TypeError::NotANumber(function.name().into(), value.type_name().into()).into())
+        }
+    }
+}
+
+/// Orders two values of the same kind, comparing integers and floats by their numbers
+pub fn compare(left: &Value, right: &Value) -> Result<Ordering, Error> {
+    let ordering = match (left, right) {
+        (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+        (Value::Integer(left), Value::Float(right)) => (*left as f32).partial_cmp(right),
+        (Value::Float(left), Value::Integer(right)) => left.partial_cmp(&(*right as f32)),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        (Value::Bool(left), Value::Bool(right)) => Some(left.cmp(right)),
+        _ => None,
+    };
+    match ordering {
+        Some(ordering) => Ok(ordering),
+        None => Err(TypeError::IllegalOperation(
+            "comparison".into(),
+            left.type_name().into(),
+            right.type_name().into(),
+        )
+        .into()),
+    }
+}
@@ -1,4 +1,4 @@
-use std::{env::args, fs};
+use std::{env::args, fs, str::FromStr, time::Duration};
 
 use anyhow::Result;
 
@@ -16,20 +16,60 @@ mod error;
 pub use error::*;
 mod parser;
 pub use parser::*;
+mod checker;
+pub use checker::*;
+mod list;
+mod math;
+mod random;
+pub use random::*;
+
+fn parse_arg<T: FromStr>(arg: Option<String>, kind: &str) -> Result<T, Error> {
+    let arg = arg.unwrap_or_default();
+    arg.parse()
+        .map_err(|_| InterpreterError::ParseError(arg, kind.to_string()).into())
+}
 
 pub fn main() -> Result<()> {
     let mut program = String::new();
+    let mut global = Global::default();
 
-    let args: Vec<String> = args().collect();
+    let mut args = args().skip(1);
 
-    if args.len() > 0 {
-        if std::path::Path::new(&args[1]).is_file() {
-            program = String::from_utf8_lossy(
-                &fs::read(std::path::Path::new(&args[1])).expect("Failed to read file"),
-            )
-            .to_string();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--semente" => global.rng.seed(parse_arg(args.next(), "seed")?),
+            "--profundidade" => global.depth_limit = Some(parse_arg(args.next(), "number")?),
+            "--memoria" => {
+                global.memory_limit =
+                    parse_arg::<usize>(args.next(), "number")?.saturating_mul(1024 * 1024)
+            }
+            "--passos" => global.step_limit = Some(parse_arg(args.next(), "number")?),
+            "--tempo" => {
+                let seconds: f64 = parse_arg(args.next(), "number")?;
+                match Duration::try_from_secs_f64(seconds) {
+                    Ok(limit) => global.time_limit = Some(limit),
+                    Err(_) => {
+                        return Err(Error::from(InterpreterError::ParseError(
+                            seconds.to_string(),
+                            "number".to_string(),
+                        ))
+                        .into())
+                    }
+                }
+            }
+            "--constantes-maiusculas" => global.uppercase_constants = true,
+            "--condicoes-flexiveis" => global.truthiness = true,
+            path => {
+                if std::path::Path::new(path).is_file() {
+                    global
+                        .files
+                        .push(fs::canonicalize(path).unwrap_or_else(|_| path.into()));
+                    program =
+                        String::from_utf8_lossy(&fs::read(std::path::Path::new(path))?).to_string();
+                }
+            }
         }
     }
 
-    run(program)
+    run(program, global)
 }
@@ -0,0 +1,117 @@
+use std::f32::consts::PI;
+
+use crate::{Error, InterpreterError, MathFn, TypeError, Value};
+
+impl MathFn {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MathFn::SquareRoot => "square root",
+            MathFn::Power => "exponentiation",
+            MathFn::Round => "rounding",
+            MathFn::Floor => "floor",
+            MathFn::Ceil => "ceiling",
+            MathFn::Abs => "absolute value",
+            MathFn::Sin => "sine",
+            MathFn::Cos => "cosine",
+            MathFn::Pi => "pi",
+            MathFn::Min => "minimum",
+            MathFn::Max => "maximum",
+        }
+    }
+
+    pub fn apply(&self, args: Vec<Value>) -> Result<Value, Error> {
+        let mut args = args.into_iter();
+        let mut next = || args.next().unwrap_or(Value::Void);
+
+        match self {
+            MathFn::Pi => Ok(Value::Float(PI)),
+            MathFn::SquareRoot => {
+                let value = next();
+                let number = self.number(&value)?;
+                if number < 0.0 {
+                    return Err(InterpreterError::MathDomain(
+                        self.name().into(),
+                        value.to_string(),
+                    )
+                    .into());
+                }
+                let root = number.sqrt();
+                match value {
+                    Value::Integer(_) if root.fract() == 0.0 => Ok(Value::Integer(root as i32)),
+                    _ => Ok(Value::Float(root)),
+                }
+            }
+            MathFn::Power => match (next(), next()) {
+                (Value::Integer(base), Value::Integer(exp)) if exp >= 0 => {
+                    match base.checked_pow(exp as u32) {
+                        Some(result) => Ok(Value::Integer(result)),
+                        None => Ok(Value::Float((base as f32).powi(exp))),
+                    }
+                }
+                (base, exp) => Ok(Value::Float(self.number(&base)?.powf(self.number(&exp)?))),
+            },
+            MathFn::Round => {
+                let value = next();
+                match next() {
+                    Value::Void => match value {
+                        Value::Integer(integer) => Ok(Value::Integer(integer)),
+                        value => self.integer(self.number(&value)?.round()),
+                    },
+                    Value::Integer(places) => {
+                        let value = self.number(&value)?;
+                        let factor = 10f32.powi(places);
+                        Ok(Value::Float((value * factor).round() / factor))
+                    }
+                    places => Err(TypeError::NotANumber(
+                        "rounding to decimal places".into(),
+                        places.type_name().into(),
+                    )
+                    .into()),
+                }
+            }
+            MathFn::Floor => match next() {
+                Value::Integer(integer) => Ok(Value::Integer(integer)),
+                value => self.integer(self.number(&value)?.floor()),
+            },
+            MathFn::Ceil => match next() {
+                Value::Integer(integer) => Ok(Value::Integer(integer)),
+                value => self.integer(self.number(&value)?.ceil()),
+            },
+            MathFn::Abs => match next() {
+                Value::Integer(integer) => match integer.checked_abs() {
+                    Some(absolute) => Ok(Value::Integer(absolute)),
+                    None => Err(InterpreterError::Overflow(self.name().into()).into()),
+                },
+                value => Ok(Value::Float(self.number(&value)?.abs())),
+            },
+            MathFn::Sin => Ok(Value::Float(self.number(&next())?.sin())),
+            MathFn::Cos => Ok(Value::Float(self.number(&next())?.cos())),
+            MathFn::Min | MathFn::Max => {
+                let (left, right) = (next(), next());
+                let smaller = self.number(&left)? <= self.number(&right)?;
+                if smaller == (*self == MathFn::Min) {
+                    Ok(left)
+                } else {
+                    Ok(right)
+                }
+            }
+        }
+    }
+
+    fn integer(&self, float: f32) -> Result<Value, Error> {
+        match Value::integer_from(float) {
+            Some(integer) => Ok(Value::Integer(integer)),
+            None => Err(InterpreterError::Overflow(self.name().into()).into()),
+        }
+    }
+
+    fn number(&self, value: &Value) -> Result<f32, Error> {
+        match value {
+            Value::Integer(integer) => Ok(*integer as f32),
+            Value::Float(float) => Ok(*float),
+            value => {
+                Err(TypeError::NotANumber(self.name().into(), value.type_name().into()).into())
+            }
+        }
+    }
+}
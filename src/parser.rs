@@ -1,6 +1,11 @@
+use std::time::Instant;
+
 use anyhow::Result;
 
-use crate::{AstNode, Error, Expression, Global, InputType, ParserError, Scope, Value};
+use crate::{
+    check_constants, check_types, nested, AstNode, ConvertType, Error, Expression, Global,
+    InputType, ListFn, MathFn, ParserError, Pattern, Scope, Type, Value,
+};
 
 use pest::Parser;
 
@@ -8,22 +13,64 @@ use pest::Parser;
 #[grammar = "ptbr.pest"]
 struct PTBRParser;
 
-pub fn run(program: String) -> Result<()> {
+pub fn run(program: String, mut global: Global) -> Result<()> {
+    global.started = Instant::now();
+    let ast = load(&program, &global)?;
+    let scope = Scope::new();
+    let result = scope.interpret_program(ast, &mut global)?;
+    scope.finish(result, &mut global)?;
+    Ok(())
+}
+
+pub fn load(program: &str, global: &Global) -> Result<Vec<Box<AstNode>>, Error> {
     let mut ast = vec![];
 
-    for pair in match PTBRParser::parse(Rule::program, &program) {
+    for pair in match PTBRParser::parse(Rule::program, program) {
         Ok(pairs) => Ok(pairs),
         Err(error) => Err(Error::from(ParserError::PestError(format!("{}", error)))),
     }? {
-        match pair.as_rule() {
-            Rule::line => {
-                ast.push(Box::new(build_ast_from_expr(pair)?));
+        if pair.as_rule() == Rule::line {
+            ast.push(Box::new(build_ast_from_expr(pair)?));
+        }
+    }
+    check_constants(&ast, global.uppercase_constants)?;
+    check_types(&ast)?;
+    Ok(ast)
+}
+
+type CallArgs = (Vec<Expression>, Vec<(String, Expression)>);
+
+fn build_call_args(signature: Option<pest::iterators::Pair<Rule>>) -> Result<CallArgs, Error> {
+    let mut vars = vec![];
+    let mut named = vec![];
+    if let Some(signature) = signature {
+        for pair in signature.into_inner() {
+            match pair.as_rule() {
+                Rule::named_arg => {
+                    let source = pair.as_str();
+                    let mut pair = pair.into_inner();
+                    let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+                    named.push((ident, build_expr(next(&mut pair, source, "value")?)?));
+                }
+                _ => vars.push(build_expr(pair)?),
             }
-            _ => {}
         }
     }
-    Scope::new().interpret_program(ast, &mut Global::default())?;
-    Ok(())
+    Ok((vars, named))
+}
+
+fn build_type(pair: pest::iterators::Pair<Rule>) -> Result<Type, Error> {
+    match pair.as_rule() {
+        Rule::tipo_numero => Ok(Type::Number),
+        Rule::tipo_inteiro => Ok(Type::Integer),
+        Rule::tipo_real => Ok(Type::Float),
+        Rule::tipo_texto => Ok(Type::String),
+        Rule::tipo_logico => Ok(Type::Bool),
+        Rule::tipo_lista => Ok(Type::List),
+        Rule::tipo_dicionario => Ok(Type::Dictionary),
+        Rule::tipo_funcao => Ok(Type::Function),
+        _ => Err(ParserError::ParseError(pair.as_str().to_string(), "type".to_string()).into()),
+    }
 }
 
 fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
@@ -48,6 +95,7 @@ fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
         )))),
 
         Rule::verdadeiro => Ok(Expression::Value(Value::Bool(true))),
+        Rule::nada => Ok(Expression::Value(Value::Void)),
 
         Rule::falso => Ok(Expression::Value(Value::Bool(false))),
 
@@ -143,6 +191,52 @@ fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
             ))
         }
 
+        Rule::power_expr => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let left = match pair.next() {
+                Some(left) => Ok(left),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "left".to_string(),
+                )),
+            }?;
+            let right = match pair.next() {
+                Some(right) => Ok(right),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "right".to_string(),
+                )),
+            }?;
+            Ok(Expression::Math(
+                MathFn::Power,
+                vec![build_expr(left)?, build_expr(right)?],
+            ))
+        }
+
+        Rule::math_call => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let function = match pair.next().map(|function| function.as_rule()) {
+                Some(Rule::raiz_quadrada) => Ok(MathFn::SquareRoot),
+                Some(Rule::piso) => Ok(MathFn::Floor),
+                Some(Rule::teto) => Ok(MathFn::Ceil),
+                Some(Rule::valor_absoluto) => Ok(MathFn::Abs),
+                Some(Rule::seno) => Ok(MathFn::Sin),
+                Some(Rule::cosseno) => Ok(MathFn::Cos),
+                Some(Rule::minimo) => Ok(MathFn::Min),
+                Some(Rule::maximo) => Ok(MathFn::Max),
+                Some(Rule::arredonde) => Ok(MathFn::Round),
+                Some(Rule::pi) => Ok(MathFn::Pi),
+                _ => Err(ParserError::NotAnExpression(expr.to_string())),
+            }?;
+            let mut args = vec![];
+            for pair in pair {
+                args.push(build_expr(pair)?);
+            }
+            Ok(Expression::Math(function, args))
+        }
+
         Rule::and_expr => {
             let expr = pair.clone().as_str();
             let mut pair = pair.into_inner();
@@ -191,6 +285,7 @@ fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
 
         Rule::function_call => {
             let expr = pair.clone().as_str();
+            let line = pair.as_span().start_pos().line_col().0;
             let mut pair = pair.into_inner();
             let ident = String::from(match pair.next() {
                 Some(pair) => Ok(pair.as_str()),
@@ -199,20 +294,217 @@ fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
                     "identifier".to_string(),
                 )),
             }?);
-            let mut vars: Vec<Expression> = vec![];
-            if let Some(signature_pair) = pair.peek() {
-                if let Rule::function_signature = signature_pair.as_rule() {
-                    for pair in pair.next().unwrap().into_inner() {
-                        vars.push(build_expr(pair)?);
-                    }
+            let (vars, named) = build_call_args(pair.next())?;
+            Ok(Expression::FnCall(ident, vars, named, line))
+        }
+
+        Rule::list => {
+            let mut list = vec![];
+            for pair in pair.into_inner() {
+                list.push(build_expr(pair)?);
+            }
+            Ok(Expression::List(list))
+        }
+
+        Rule::dictionary => {
+            let mut entries = vec![];
+            for entry in pair.into_inner() {
+                let expr = entry.clone().as_str();
+                let mut entry = entry.into_inner();
+                let key = match entry.next() {
+                    Some(key) => Ok(key),
+                    None => Err(ParserError::IncompleteExpr(
+                        expr.to_string(),
+                        "key".to_string(),
+                    )),
+                }?;
+                let value = match entry.next() {
+                    Some(value) => Ok(value),
+                    None => Err(ParserError::IncompleteExpr(
+                        expr.to_string(),
+                        "value".to_string(),
+                    )),
+                }?;
+                entries.push((build_expr(key)?, build_expr(value)?));
+            }
+            Ok(Expression::Dictionary(entries))
+        }
+
+        Rule::lookup | Rule::has_key => {
+            let expr = pair.clone().as_str();
+            let rule = pair.as_rule();
+            let mut pair = pair.into_inner();
+            let ident = match pair.next() {
+                Some(ident) => Ok(String::from(ident.as_str())),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "dictionary".to_string(),
+                )),
+            }?;
+            let key = match pair.next() {
+                Some(key) => Ok(Box::new(build_expr(key)?)),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "key".to_string(),
+                )),
+            }?;
+            match rule {
+                Rule::lookup => Ok(Expression::Lookup(ident, key)),
+                _ => Ok(Expression::HasKey(ident, key)),
+            }
+        }
+
+        Rule::new_record => {
+            let source = pair.as_str();
+            let mut pair = pair.into_inner();
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let mut values = vec![];
+            if let Some(signature) = pair.next() {
+                for pair in signature.into_inner() {
+                    values.push(build_expr(pair)?);
                 }
             }
-            Ok(Expression::FnCall(ident, vars))
+            Ok(Expression::NewRecord(ident, values))
+        }
+
+        Rule::field_access => {
+            let source = pair.as_str();
+            let mut pair = pair.into_inner();
+            let field = String::from(next(&mut pair, source, "field")?.as_str());
+            let record = build_expr(next(&mut pair, source, "record")?)?;
+            Ok(Expression::Field(field, Box::new(record)))
+        }
+
+        Rule::anonymous_function => {
+            let source = pair.as_str();
+            let mut pair = pair.into_inner();
+            let mut args: Vec<String> = vec![];
+            if let Some(Rule::function_signature) = pair.peek().map(|pair| pair.as_rule()) {
+                for pair in next(&mut pair, source, "arguments")?.into_inner() {
+                    args.push(String::from(pair.as_str()));
+                }
+            }
+            let body = next(&mut pair, source, "body")?;
+            let block = match body.as_rule() {
+                Rule::block_section => build_block(body)?,
+                _ => vec![Box::new(AstNode::Return(build_expr(body)?))],
+            };
+            Ok(Expression::AnonymousFunction(args, block))
+        }
+
+        Rule::list_call => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let function = match pair.next().map(|function| function.as_rule()) {
+                Some(Rule::mapear) => Ok(ListFn::Map),
+                Some(Rule::filtrar) => Ok(ListFn::Filter),
+                Some(Rule::reduzir) => Ok(ListFn::Reduce),
+                Some(Rule::ordenar) => Ok(ListFn::Sort),
+                Some(Rule::algum) => Ok(ListFn::Any),
+                Some(Rule::todos) => Ok(ListFn::All),
+                Some(Rule::soma) => Ok(ListFn::Sum),
+                Some(Rule::media) => Ok(ListFn::Mean),
+                Some(Rule::mediana) => Ok(ListFn::Median),
+                Some(Rule::maior) => Ok(ListFn::Max),
+                Some(Rule::menor) => Ok(ListFn::Min),
+                _ => Err(ParserError::NotAnExpression(expr.to_string())),
+            }?;
+            let mut args = vec![];
+            for pair in pair {
+                args.push(build_expr(pair)?);
+            }
+            Ok(Expression::ListOp(function, args))
+        }
+
+        Rule::random_number => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let low = match pair.next() {
+                Some(low) => Ok(low),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "lower".to_string(),
+                )),
+            }?;
+            let high = match pair.next() {
+                Some(high) => Ok(high),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "upper".to_string(),
+                )),
+            }?;
+            Ok(Expression::RandomNumber(
+                Box::new(build_expr(low)?),
+                Box::new(build_expr(high)?),
+            ))
         }
 
-        Rule::entrada_numero => Ok(Expression::Entrada(InputType::Number)),
+        Rule::random_item => {
+            let expr = pair.clone().as_str();
+            let list = match pair.into_inner().next() {
+                Some(list) => Ok(list),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "list".to_string(),
+                )),
+            }?;
+            Ok(Expression::RandomItem(Box::new(build_expr(list)?)))
+        }
 
-        Rule::entrada_texto => Ok(Expression::Entrada(InputType::String)),
+        Rule::conversion => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let value = match pair.next() {
+                Some(value) => Ok(value),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "converted".to_string(),
+                )),
+            }?;
+            let to = match pair.next().map(|to| to.as_rule()) {
+                Some(Rule::para_numero) => Ok(ConvertType::Number),
+                Some(Rule::para_inteiro) => Ok(ConvertType::Integer),
+                Some(Rule::para_texto) => Ok(ConvertType::String),
+                _ => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "type".to_string(),
+                )),
+            }?;
+            Ok(Expression::Convert(Box::new(build_expr(value)?), to))
+        }
+
+        Rule::type_of => {
+            let expr = pair.clone().as_str();
+            let value = match pair.into_inner().next() {
+                Some(value) => Ok(value),
+                None => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "inspected".to_string(),
+                )),
+            }?;
+            Ok(Expression::TypeOf(Box::new(build_expr(value)?)))
+        }
+
+        Rule::entrada => {
+            let expr = pair.clone().as_str();
+            let mut pair = pair.into_inner();
+            let input_type = match pair.next().map(|input_type| input_type.as_rule()) {
+                Some(Rule::entrada_numero) => Ok(InputType::Number),
+                Some(Rule::entrada_inteiro) => Ok(InputType::Integer),
+                Some(Rule::entrada_texto) => Ok(InputType::String),
+                Some(Rule::entrada_sim_nao) => Ok(InputType::Bool),
+                Some(Rule::entrada_lista_numeros) => Ok(InputType::NumberList),
+                _ => Err(ParserError::IncompleteExpr(
+                    expr.to_string(),
+                    "type".to_string(),
+                )),
+            }?;
+            let prompt = match pair.next() {
+                Some(prompt) => Some(Box::new(build_expr(prompt)?)),
+                None => None,
+            };
+            Ok(Expression::Entrada(input_type, prompt))
+        }
 
         Rule::comp_expr => {
             let expr = pair.clone().as_str();
@@ -260,58 +552,74 @@ fn build_expr(pair: pest::iterators::Pair<Rule>) -> Result<Expression, Error> {
     }
 }
 
+fn incomplete(source: &str, part: &str) -> Error {
+    ParserError::IncompleteExpr(source.to_string(), part.to_string()).into()
+}
+
+/// Takes the next part of `source`, failing when the parser didn't produce it
+fn next<'i>(
+    pairs: &mut pest::iterators::Pairs<'i, Rule>,
+    source: &str,
+    part: &str,
+) -> Result<pest::iterators::Pair<'i, Rule>, Error> {
+    pairs.next().ok_or_else(|| incomplete(source, part))
+}
+
+pub fn build_block(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Box<AstNode>>, Error> {
+    pair.into_inner()
+        .map(|pair| build_ast_from_expr(pair).map(Box::new))
+        .collect()
+}
+
 fn build_ast_from_expr(pair: pest::iterators::Pair<Rule>) -> Result<AstNode, Error> {
+    nested(|| build_statement(pair))
+}
+
+fn build_statement(pair: pest::iterators::Pair<Rule>) -> Result<AstNode, Error> {
+    let source = pair.as_str();
     match pair.as_rule() {
-        Rule::line => build_ast_from_expr(pair.into_inner().next().unwrap()),
+        Rule::line => build_ast_from_expr(next(&mut pair.into_inner(), source, "statement")?),
         Rule::se => {
             let mut pair = pair.into_inner();
-            let comp_expr = pair.next().unwrap();
+            let comp_expr = next(&mut pair, source, "condition")?;
             Ok(AstNode::If {
+                source: String::from(comp_expr.as_str()),
                 comp: build_expr(comp_expr)?,
-                block: pair
-                    .next()
-                    .unwrap()
-                    .into_inner()
-                    .into_iter()
-                    .map(|pair| Box::new(build_ast_from_expr(pair).unwrap()))
-                    .collect(),
-                senao: {
-                    if let Some(senao_block) = pair.next() {
-                        Some(
-                            senao_block
-                                .into_inner()
-                                .next()
-                                .unwrap()
-                                .into_inner()
-                                .into_iter()
-                                .map(|pair| Box::new(build_ast_from_expr(pair).unwrap()))
-                                .collect(),
-                        )
-                    } else {
-                        None
-                    }
+                block: build_block(next(&mut pair, source, "block")?)?,
+                senao: match pair.next() {
+                    Some(senao_block) => Some(build_block(next(
+                        &mut senao_block.into_inner(),
+                        source,
+                        "block",
+                    )?)?),
+                    None => None,
                 },
             })
         }
         Rule::enquanto => {
             let mut pair = pair.into_inner();
-            let comp_expr = pair.next().unwrap();
+            let comp_expr = next(&mut pair, source, "condition")?;
             Ok(AstNode::While {
+                source: String::from(comp_expr.as_str()),
                 comp: build_expr(comp_expr)?,
-                block: pair
-                    .next()
-                    .unwrap()
-                    .into_inner()
-                    .into_iter()
-                    .map(|pair| Box::new(build_ast_from_expr(pair).unwrap()))
-                    .collect(),
+                block: build_block(next(&mut pair, source, "block")?)?,
             })
         }
+        Rule::constante => {
+            match build_ast_from_expr(next(&mut pair.into_inner(), source, "definition")?)? {
+                AstNode::Definition { ident, expr, kind } => {
+                    Ok(AstNode::Constant { ident, expr, kind })
+                }
+                ast => Err(ParserError::NotAST(format!("{:?}", ast)).into()),
+            }
+        }
         Rule::define_variable => {
             let mut pair = pair.into_inner();
-            let ident = String::from(pair.next().unwrap().as_str());
-            let expr = build_expr(pair.next().unwrap())?;
-            Ok(AstNode::Definition { ident, expr })
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let mut pair: Vec<_> = pair.collect();
+            let expr = build_expr(pair.pop().ok_or_else(|| incomplete(source, "value"))?)?;
+            let kind = pair.pop().map(build_type).transpose()?;
+            Ok(AstNode::Definition { ident, expr, kind })
         }
         Rule::mostre => {
             let mut vec: Vec<Expression> = Vec::new();
@@ -322,38 +630,211 @@ fn build_ast_from_expr(pair: pest::iterators::Pair<Rule>) -> Result<AstNode, Err
         }
         Rule::function => {
             let mut pair = pair.into_inner();
-            let ident = String::from(pair.next().unwrap().as_str());
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
             let mut args: Vec<String> = vec![];
-            if let Rule::function_signature = pair.peek().unwrap().as_rule() {
-                for pair in pair.next().unwrap().into_inner() {
-                    args.push(String::from(pair.as_str()));
+            let mut types = vec![];
+            let mut defaults = vec![];
+            if let Some(Rule::parameters) = pair.peek().map(|pair| pair.as_rule()) {
+                for parameter in next(&mut pair, source, "parameters")?.into_inner() {
+                    let mut parameter = parameter.into_inner();
+                    args.push(String::from(
+                        next(&mut parameter, source, "parameter")?.as_str(),
+                    ));
+                    let mut kind = None;
+                    let mut default = None;
+                    for pair in parameter {
+                        match pair.as_rule() {
+                            Rule::default_value => {
+                                default = Some(build_expr(next(
+                                    &mut pair.into_inner(),
+                                    source,
+                                    "default",
+                                )?)?)
+                            }
+                            _ => kind = Some(build_type(pair)?),
+                        }
+                    }
+                    types.push(kind);
+                    defaults.push(default);
                 }
             }
-            let block = pair
-                .next()
-                .unwrap()
-                .into_inner()
+            let returns = match pair.peek().map(|pair| pair.as_rule()) {
+                Some(Rule::block_section) | None => None,
+                _ => Some(build_type(next(&mut pair, source, "type")?)?),
+            };
+            let block = build_block(next(&mut pair, source, "block")?)?;
+            Ok(AstNode::Function {
+                ident,
+                args,
+                types,
+                defaults,
+                returns,
+                block,
+            })
+        }
+        Rule::function_call => {
+            let line = pair.as_span().start_pos().line_col().0;
+            let mut pair = pair.into_inner();
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let (vars, named) = build_call_args(pair.next())?;
+            Ok(AstNode::FnCall {
+                ident,
+                vars,
+                named,
+                line,
+            })
+        }
+        Rule::retorne => {
+            let mut values = vec![];
+            for pair in pair.into_inner() {
+                values.push(build_expr(pair)?);
+            }
+            match values.len() {
+                0 => Err(incomplete(source, "value")),
+                1 => Ok(AstNode::Return(values.remove(0))),
+                _ => Ok(AstNode::Return(Expression::Tuple(values))),
+            }
+        }
+        Rule::destructure => {
+            let mut pair: Vec<_> = pair.into_inner().collect();
+            let expr = build_expr(pair.pop().ok_or_else(|| incomplete(source, "value"))?)?;
+            let idents = pair
                 .into_iter()
-                .map(|pair| Box::new(build_ast_from_expr(pair).unwrap()))
+                .map(|ident| String::from(ident.as_str()))
                 .collect();
-            Ok(AstNode::Function { ident, args, block })
+            Ok(AstNode::Destructure { idents, expr })
         }
-        Rule::function_call => {
+        Rule::define_key => {
+            let mut pair = pair.into_inner();
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let key = build_expr(next(&mut pair, source, "key")?)?;
+            let expr = build_expr(next(&mut pair, source, "value")?)?;
+            Ok(AstNode::DefineKey { ident, key, expr })
+        }
+        Rule::remove_key => {
+            let mut pair = pair.into_inner();
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let key = build_expr(next(&mut pair, source, "key")?)?;
+            Ok(AstNode::RemoveKey { ident, key })
+        }
+        Rule::para_cada => {
             let mut pair = pair.into_inner();
-            let ident = String::from(pair.next().unwrap().as_str());
-            let mut vars: Vec<Expression> = vec![];
-            if let Some(signature_pair) = pair.peek() {
-                if let Rule::function_signature = signature_pair.as_rule() {
-                    for pair in pair.next().unwrap().into_inner() {
-                        vars.push(build_expr(pair)?);
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let iterable = build_expr(next(&mut pair, source, "iterable")?)?;
+            let block = build_block(next(&mut pair, source, "block")?)?;
+            Ok(AstNode::ForEach {
+                ident,
+                iterable,
+                block,
+            })
+        }
+        Rule::record => {
+            let mut pair = pair.into_inner();
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let fields = pair.map(|field| String::from(field.as_str())).collect();
+            Ok(AstNode::Record { ident, fields })
+        }
+        Rule::define_field => {
+            let mut pair = pair.into_inner();
+            let field = String::from(next(&mut pair, source, "field")?.as_str());
+            let ident = String::from(next(&mut pair, source, "identifier")?.as_str());
+            let expr = build_expr(next(&mut pair, source, "value")?)?;
+            Ok(AstNode::DefineField { ident, field, expr })
+        }
+        Rule::semente => Ok(AstNode::Seed(build_expr(next(
+            &mut pair.into_inner(),
+            source,
+            "seed",
+        )?)?)),
+        Rule::tente => {
+            let mut pair = pair.into_inner();
+            let block = build_block(next(&mut pair, source, "block")?)?;
+            let mut catch = next(&mut pair, source, "block")?;
+            let ident = match catch.as_rule() {
+                Rule::ident => {
+                    let ident = String::from(catch.as_str());
+                    catch = next(&mut pair, source, "block")?;
+                    Some(ident)
+                }
+                _ => None,
+            };
+            Ok(AstNode::Try {
+                block,
+                ident,
+                catch: build_block(catch)?,
+            })
+        }
+        Rule::escolha => {
+            let mut pair = pair.into_inner();
+            let subject = next(&mut pair, source, "value")?;
+            let source = subject.as_str();
+            let expr = build_expr(subject.clone())?;
+            let mut cases = vec![];
+            let mut default = None;
+            let mut seen: Vec<Pattern> = vec![];
+            for case in pair {
+                let rule = case.as_rule();
+                let mut inner: Vec<_> = case.into_inner().collect();
+                let block = build_block(inner.pop().ok_or_else(|| incomplete(source, "block"))?)?;
+                if rule == Rule::caso_contrario {
+                    default = Some(block);
+                    continue;
+                }
+                let mut patterns = vec![];
+                for pattern in inner {
+                    let text = pattern.as_str();
+                    let pattern = match pattern.as_rule() {
+                        Rule::faixa => {
+                            let mut bounds = pattern.into_inner();
+                            Pattern::Range(
+                                build_expr(next(&mut bounds, text, "left")?)?,
+                                build_expr(next(&mut bounds, text, "right")?)?,
+                            )
+                        }
+                        _ => Pattern::Value(build_expr(pattern)?),
+                    };
+                    if seen.contains(&pattern) {
+                        eprintln!("Warning: case {} is repeated in escolha {}", text, source);
+                    } else {
+                        seen.push(pattern.clone());
                     }
+                    patterns.push(pattern);
                 }
+                cases.push((patterns, block));
             }
-            Ok(AstNode::FnCall { ident, vars })
+            Ok(AstNode::Match {
+                expr,
+                cases,
+                default,
+            })
+        }
+        Rule::importe => {
+            let mut pair = pair.into_inner();
+            let path = next(&mut pair, source, "path")?;
+            let path = next(&mut path.into_inner(), source, "path")?.as_str();
+            Ok(AstNode::Import {
+                path: String::from(path),
+                variables: pair.next().is_some(),
+            })
+        }
+        Rule::garanta => {
+            let mut pair = pair.into_inner();
+            let comp = next(&mut pair, source, "condition")?;
+            let source = String::from(comp.as_str());
+            Ok(AstNode::Assert {
+                comp: build_expr(comp)?,
+                source,
+                message: match pair.next() {
+                    Some(message) => Some(build_expr(message)?),
+                    None => None,
+                },
+            })
         }
-        Rule::retorne => Ok(AstNode::Return(build_expr(
-            pair.into_inner().next().unwrap(),
-        )?)),
+        Rule::lance => Ok(AstNode::Throw(build_expr(next(
+            &mut pair.into_inner(),
+            source,
+            "value",
+        )?)?)),
 
         _ => {
             //println!("pair not implemented: {:#?}", pair);
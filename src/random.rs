@@ -0,0 +1,86 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Error, InterpreterError, TypeError, Value};
+
+#[derive(Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn seed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    pub fn number_between(&mut self, low: Value, high: Value) -> Result<Value, Error> {
+        match (low, high) {
+            (Value::Integer(low), Value::Integer(high)) => {
+                let (low, high) = (low.min(high), low.max(high));
+                let range = (high as i64 - low as i64 + 1) as u64;
+                Ok(Value::Integer(
+                    (low as i64 + self.below(range) as i64) as i32,
+                ))
+            }
+            (low, high) => {
+                let low = number(low)?;
+                let high = number(high)?;
+                Ok(Value::Float(low + (high - low) * self.next_f32()))
+            }
+        }
+    }
+
+    pub fn item_of(&mut self, list: Value) -> Result<Value, Error> {
+        match list {
+            Value::List(list) if list.is_empty() => {
+                Err(InterpreterError::EmptyList("random item".into()).into())
+            }
+            Value::List(mut list) => {
+                let index = self.below(list.len() as u64) as usize;
+                Ok(list.swap_remove(index))
+            }
+            value => {
+                Err(TypeError::NotAList("random item".into(), value.type_name().into()).into())
+            }
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        let seed = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.as_nanos() as u64,
+            Err(_) => 0,
+        };
+        Self::seeded(seed)
+    }
+}
+
+fn number(value: Value) -> Result<f32, Error> {
+    match value {
+        Value::Integer(integer) => Ok(integer as f32),
+        Value::Float(float) => Ok(float),
+        value => {
+            Err(TypeError::NotANumber("random number".into(), value.type_name().into()).into())
+        }
+    }
+}
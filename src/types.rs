@@ -1,12 +1,12 @@
 use core::fmt::{self, Display, Formatter};
 use std::{
-    convert::TryFrom,
     ops::{Add, Div, Mul, Sub},
+    rc::Rc,
 };
 
 use anyhow::Result;
 
-use crate::{Error, TypeError};
+use crate::{Error, Function, InterpreterError, TypeError, VoidSource};
 
 #[derive(Debug, PartialEq, Clone, PartialOrd)]
 pub enum Value {
@@ -15,13 +15,330 @@ pub enum Value {
     Integer(i32),
     Float(f32),
     Bool(bool),
-    //List(Vec<Value>),
+    List(Vec<Value>),
+    Dictionary(Vec<(Value, Value)>),
+    Record(String, Vec<(String, Value)>),
+    Function(Rc<Function>),
+    Tuple(Vec<Value>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum InputType {
     Number,
+    Integer,
     String,
+    Bool,
+    NumberList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Type {
+    Any,
+    Void,
+    Number,
+    Integer,
+    Float,
+    String,
+    Bool,
+    List,
+    Dictionary,
+    Record(String),
+    Function,
+    Tuple,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConvertType {
+    Number,
+    Integer,
+    String,
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Void => "void",
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "bool",
+            Value::List(_) => "list",
+            Value::Dictionary(_) => "dictionary",
+            Value::Record(_, _) => "record",
+            Value::Function(_) => "function",
+            Value::Tuple(_) => "tuple",
+        }
+    }
+
+    pub fn ptbr_type_name(&self) -> String {
+        match self {
+            Value::Void => "nada".into(),
+            Value::String(_) => "texto".into(),
+            Value::Integer(_) => "inteiro".into(),
+            Value::Float(_) => "real".into(),
+            Value::Bool(_) => "lógico".into(),
+            Value::List(_) => "lista".into(),
+            Value::Dictionary(_) => "dicionário".into(),
+            Value::Record(name, _) => name.clone(),
+            Value::Function(_) => "função".into(),
+            Value::Tuple(_) => "valores".into(),
+        }
+    }
+
+    pub fn parse_number(text: &str) -> Option<Value> {
+        let text = text.trim();
+        match text.parse::<i32>() {
+            Ok(integer) => Some(Value::Integer(integer)),
+            Err(_) => match text.replace(',', ".").parse::<f32>() {
+                Ok(float) if float.is_finite() => Some(Value::Float(float)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Truncates a float to an integer, if it is finite and fits in one
+    pub fn integer_from(float: f32) -> Option<i32> {
+        (-2147483648.0..2147483648.0)
+            .contains(&float)
+            .then_some(float as i32)
+    }
+
+    pub fn convert(self, to: ConvertType) -> Result<Value, Error> {
+        match (to, self) {
+            (ConvertType::String, value) => Ok(Value::String(value.to_string())),
+            (ConvertType::Number, Value::Integer(integer)) => Ok(Value::Integer(integer)),
+            (ConvertType::Number, Value::Float(float)) => Ok(Value::Float(float)),
+            (ConvertType::Integer, Value::Integer(integer)) => Ok(Value::Integer(integer)),
+            (ConvertType::Integer, Value::Float(float)) => match Value::integer_from(float) {
+                Some(integer) => Ok(Value::Integer(integer)),
+                None => Err(InterpreterError::ParseError(
+                    float.to_string(),
+                    ConvertType::Integer.name().into(),
+                )
+                .into()),
+            },
+            (to, Value::String(string)) => match (to, Value::parse_number(&string)) {
+                (ConvertType::Integer, Some(Value::Float(float))) => {
+                    match Value::integer_from(float) {
+                        Some(integer) => Ok(Value::Integer(integer)),
+                        None => Err(InterpreterError::ParseError(
+                            string,
+                            ConvertType::Integer.name().into(),
+                        )
+                        .into()),
+                    }
+                }
+                (_, Some(number)) => Ok(number),
+                (to, None) => Err(InterpreterError::ParseError(string, to.name().into()).into()),
+            },
+            (to, value) => {
+                Err(InterpreterError::ParseError(value.to_string(), to.name().into()).into())
+            }
+        }
+    }
+
+    pub fn get_key(&self, key: &Value) -> Result<Value, Error> {
+        match self {
+            Value::Dictionary(dictionary) => {
+                match dictionary.iter().find(|(existing, _)| existing == key) {
+                    Some((_, value)) => Ok(value.clone()),
+                    None => Err(InterpreterError::MissingKey(key.to_string()).into()),
+                }
+            }
+            value => {
+                Err(TypeError::NotADictionary("key lookup".into(), value.type_name().into()).into())
+            }
+        }
+    }
+
+    pub fn has_key(&self, key: &Value) -> Result<bool, Error> {
+        match self {
+            Value::Dictionary(dictionary) => {
+                Ok(dictionary.iter().any(|(existing, _)| existing == key))
+            }
+            value => Err(TypeError::NotADictionary(
+                "key membership test".into(),
+                value.type_name().into(),
+            )
+            .into()),
+        }
+    }
+
+    pub fn insert_key(&mut self, key: Value, value: Value) -> Result<(), Error> {
+        match self {
+            Value::Dictionary(dictionary) => {
+                match dictionary.iter_mut().find(|(existing, _)| *existing == key) {
+                    Some((_, existing)) => *existing = value,
+                    None => dictionary.push((key, value)),
+                }
+                Ok(())
+            }
+            value => Err(TypeError::NotADictionary(
+                "key insertion".into(),
+                value.type_name().into(),
+            )
+            .into()),
+        }
+    }
+
+    pub fn remove_key(&mut self, key: &Value) -> Result<(), Error> {
+        match self {
+            Value::Dictionary(dictionary) => {
+                match dictionary.iter().position(|(existing, _)| existing == key) {
+                    Some(index) => {
+                        dictionary.remove(index);
+                        Ok(())
+                    }
+                    None => Err(InterpreterError::MissingKey(key.to_string()).into()),
+                }
+            }
+            value => Err(
+                TypeError::NotADictionary("key removal".into(), value.type_name().into()).into(),
+            ),
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Result<Value, Error> {
+        match self {
+            Value::Record(name, fields) => {
+                match fields.iter().find(|(existing, _)| existing == field) {
+                    Some((_, value)) => Ok(value.clone()),
+                    None => Err(TypeError::UnknownField(field.into(), name.clone()).into()),
+                }
+            }
+            value => Err(TypeError::NotARecord(field.into(), value.type_name().into()).into()),
+        }
+    }
+
+    pub fn set_field(&mut self, field: &str, value: Value) -> Result<(), Error> {
+        match self {
+            Value::Record(name, fields) => {
+                match fields.iter_mut().find(|(existing, _)| existing == field) {
+                    Some((_, existing)) => {
+                        *existing = value;
+                        Ok(())
+                    }
+                    None => Err(TypeError::UnknownField(field.into(), name.clone()).into()),
+                }
+            }
+            value => Err(TypeError::NotARecord(field.into(), value.type_name().into()).into()),
+        }
+    }
+
+    pub fn items(self) -> Result<Vec<Value>, Error> {
+        match self {
+            Value::List(list) => Ok(list),
+            Value::Dictionary(dictionary) => {
+                Ok(dictionary.into_iter().map(|(key, _)| key).collect())
+            }
+            value => Err(TypeError::NotAList("iteration".into(), value.type_name().into()).into()),
+        }
+    }
+
+    /// Whether the value counts as true in a condition when running with `--condicoes-flexiveis`
+    pub fn truthy(&self) -> bool {
+        match self {
+            Value::Void => false,
+            Value::Bool(boolean) => *boolean,
+            Value::Integer(integer) => *integer != 0,
+            Value::Float(float) => *float != 0.0,
+            Value::String(string) => !string.is_empty(),
+            Value::List(list) => !list.is_empty(),
+            Value::Dictionary(dictionary) => !dictionary.is_empty(),
+            Value::Record(_, _) | Value::Function(_) | Value::Tuple(_) => true,
+        }
+    }
+}
+
+impl InputType {
+    pub fn parse(&self, input: &str) -> Result<Value, Error> {
+        let input = input.trim();
+        match self {
+            InputType::Number => match Value::parse_number(input) {
+                Some(number) => Ok(number),
+                None => Err(InterpreterError::ParseError(input.into(), "number".into()).into()),
+            },
+            InputType::Integer => match input.parse::<i32>() {
+                Ok(integer) => Ok(Value::Integer(integer)),
+                Err(_) => Err(InterpreterError::ParseError(input.into(), "integer".into()).into()),
+            },
+            InputType::String => Ok(Value::String(input.to_string())),
+            InputType::Bool => match input.to_lowercase().as_str() {
+                "sim" | "s" => Ok(Value::Bool(true)),
+                "não" | "nao" | "n" => Ok(Value::Bool(false)),
+                _ => Err(
+                    InterpreterError::ParseError(input.into(), "yes or no answer".into()).into(),
+                ),
+            },
+            InputType::NumberList => {
+                let mut list = vec![];
+                for item in input
+                    .split(|c: char| c.is_whitespace() || c == ';')
+                    .filter(|item| !item.is_empty())
+                {
+                    list.push(InputType::Number.parse(item)?);
+                }
+                Ok(Value::List(list))
+            }
+        }
+    }
+}
+
+impl Type {
+    pub fn of(value: &Value) -> Type {
+        match value {
+            Value::Void => Type::Void,
+            Value::String(_) => Type::String,
+            Value::Integer(_) => Type::Integer,
+            Value::Float(_) => Type::Float,
+            Value::Bool(_) => Type::Bool,
+            Value::List(_) => Type::List,
+            Value::Dictionary(_) => Type::Dictionary,
+            Value::Record(name, _) => Type::Record(name.clone()),
+            Value::Function(_) => Type::Function,
+            Value::Tuple(_) => Type::Tuple,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Type::Any => "any".into(),
+            Type::Void => "void".into(),
+            Type::Number => "number".into(),
+            Type::Integer => "integer".into(),
+            Type::Float => "float".into(),
+            Type::String => "string".into(),
+            Type::Bool => "bool".into(),
+            Type::List => "list".into(),
+            Type::Dictionary => "dictionary".into(),
+            Type::Record(name) => name.clone(),
+            Type::Function => "function".into(),
+            Type::Tuple => "tuple".into(),
+        }
+    }
+
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::Any, _) | (_, Type::Any) => true,
+            (Type::Number, Type::Integer | Type::Float)
+            | (Type::Integer | Type::Float, Type::Number) => true,
+            (expected, found) => expected == found,
+        }
+    }
+
+    pub fn matches(&self, value: &Value) -> bool {
+        self.accepts(&Type::of(value))
+    }
+}
+
+impl ConvertType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConvertType::Number => "number",
+            ConvertType::Integer => "integer",
+            ConvertType::String => "string",
+        }
+    }
 }
 
 impl From<bool> for Value {
@@ -35,9 +352,30 @@ impl Add for Value {
 
     fn add(self, other: Self) -> Result<Self, Error> {
         match (self, other) {
-            // (Value::List(_), _) | (_, Value::List(_)) => todo!(),
-            (Value::Void, Value::Void) => Ok(Value::Void),
-            (Value::Void, other) | (other, Value::Void) => Ok(other),
+            (Value::Void, _) | (_, Value::Void) => {
+                Err(TypeError::VoidOperation("addition".into(), VoidSource::Value).into())
+            }
+            (
+                _,
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+            )
+            | (
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+                _,
+            ) => Err(TypeError::IllegalOperation(
+                "addition".into(),
+                "any".into(),
+                value.type_name().into(),
+            )
+            .into()),
             (_value, Value::Bool(_bool)) | (Value::Bool(_bool), _value) => Err(
                 TypeError::IllegalOperation("addition".into(), "any".into(), "bool".into()).into(),
             ),
@@ -49,9 +387,10 @@ impl Add for Value {
                 Ok(Value::Float(integer as f32 + float))
             }
             (Value::Float(floatl), Value::Float(floatr)) => Ok(Value::Float(floatl + floatr)),
-            (Value::Integer(integerl), Value::Integer(integerr)) => {
-                Ok(Value::Integer(integerl + integerr))
-            }
+            (Value::Integer(integerl), Value::Integer(integerr)) => integerl
+                .checked_add(integerr)
+                .map(Value::Integer)
+                .ok_or_else(|| InterpreterError::Overflow("addition".into()).into()),
         }
     }
 }
@@ -61,9 +400,30 @@ impl Sub for Value {
 
     fn sub(self, other: Self) -> Result<Self, Error> {
         match (self, other) {
-            // (Value::List(_), _) | (_, Value::List(_)) => todo!(),
-            (Value::Void, Value::Void) => Ok(Value::Void),
-            (Value::Void, other) | (other, Value::Void) => Ok(other),
+            (Value::Void, _) | (_, Value::Void) => {
+                Err(TypeError::VoidOperation("subtraction".into(), VoidSource::Value).into())
+            }
+            (
+                _,
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+            )
+            | (
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+                _,
+            ) => Err(TypeError::IllegalOperation(
+                "subtraction".into(),
+                "any".into(),
+                value.type_name().into(),
+            )
+            .into()),
             (_value, Value::Bool(_bool)) | (Value::Bool(_bool), _value) => Err(
                 TypeError::IllegalOperation("subtraction".into(), "any".into(), "bool".into())
                     .into(),
@@ -79,9 +439,10 @@ impl Sub for Value {
                 Ok(Value::Float(float - integer as f32))
             }
             (Value::Float(floatl), Value::Float(floatr)) => Ok(Value::Float(floatl - floatr)),
-            (Value::Integer(integerl), Value::Integer(integerr)) => {
-                Ok(Value::Integer(integerl - integerr))
-            }
+            (Value::Integer(integerl), Value::Integer(integerr)) => integerl
+                .checked_sub(integerr)
+                .map(Value::Integer)
+                .ok_or_else(|| InterpreterError::Overflow("subtraction".into()).into()),
         }
     }
 }
@@ -91,9 +452,30 @@ impl Mul for Value {
 
     fn mul(self, other: Self) -> Result<Self, Error> {
         match (self, other) {
-            // (Value::List(_), _) | (_, Value::List(_)) => todo!(),
-            (Value::Void, Value::Void) => Ok(Value::Void),
-            (Value::Void, other) | (other, Value::Void) => Ok(other),
+            (Value::Void, _) | (_, Value::Void) => {
+                Err(TypeError::VoidOperation("multiplication".into(), VoidSource::Value).into())
+            }
+            (
+                _,
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+            )
+            | (
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+                _,
+            ) => Err(TypeError::IllegalOperation(
+                "multiplication".into(),
+                "any".into(),
+                value.type_name().into(),
+            )
+            .into()),
             (_value, Value::Bool(_bool)) | (Value::Bool(_bool), _value) => Err(
                 TypeError::IllegalOperation("multiplication".into(), "any".into(), "bool".into())
                     .into(),
@@ -107,9 +489,10 @@ impl Mul for Value {
                 Ok(Value::Float(integer as f32 * float))
             }
             (Value::Float(floatl), Value::Float(floatr)) => Ok(Value::Float(floatl * floatr)),
-            (Value::Integer(integerl), Value::Integer(integerr)) => {
-                Ok(Value::Integer(integerl * integerr))
-            }
+            (Value::Integer(integerl), Value::Integer(integerr)) => integerl
+                .checked_mul(integerr)
+                .map(Value::Integer)
+                .ok_or_else(|| InterpreterError::Overflow("multiplication".into()).into()),
         }
     }
 }
@@ -119,9 +502,30 @@ impl Div for Value {
 
     fn div(self, other: Self) -> Result<Self, Error> {
         match (self, other) {
-            // (Value::List(_), _) | (_, Value::List(_)) => todo!(),
-            (Value::Void, Value::Void) => Ok(Value::Void),
-            (Value::Void, other) | (other, Value::Void) => Ok(other),
+            (Value::Void, _) | (_, Value::Void) => {
+                Err(TypeError::VoidOperation("division".into(), VoidSource::Value).into())
+            }
+            (
+                _,
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+            )
+            | (
+                value @ (Value::List(_)
+                | Value::Dictionary(_)
+                | Value::Record(_, _)
+                | Value::Function(_)
+                | Value::Tuple(_)),
+                _,
+            ) => Err(TypeError::IllegalOperation(
+                "division".into(),
+                "any".into(),
+                value.type_name().into(),
+            )
+            .into()),
             (_value, Value::Bool(_bool)) | (Value::Bool(_bool), _value) => Err(
                 TypeError::IllegalOperation("division".into(), "any".into(), "bool".into()).into(),
             ),
@@ -136,9 +540,11 @@ impl Div for Value {
                 Ok(Value::Float(float / integer as f32))
             }
             (Value::Float(floatl), Value::Float(floatr)) => Ok(Value::Float(floatl / floatr)),
-            (Value::Integer(integerl), Value::Integer(integerr)) => {
-                Ok(Value::Integer(integerl / integerr))
-            }
+            (Value::Integer(_), Value::Integer(0)) => Err(InterpreterError::DivisionByZero.into()),
+            (Value::Integer(integerl), Value::Integer(integerr)) => integerl
+                .checked_div(integerr)
+                .map(Value::Integer)
+                .ok_or_else(|| InterpreterError::Overflow("division".into()).into()),
         }
     }
 }
@@ -146,14 +552,44 @@ impl Div for Value {
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            // Value::List(list) => {
-            //     let mut result = String::new();
-            //     for value in list {
-            //         result.push_str(format!("{}, ", value).as_str());
-            //     }
-            //     write!(f, "{}", &result.as_str()[0..result.len() - 2])
-            // }
-            Value::Void => write!(f, ""),
+            Value::List(list) => write!(
+                f,
+                "{}",
+                list.iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Dictionary(dictionary) => write!(
+                f,
+                "{}",
+                dictionary
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Record(name, fields) => write!(
+                f,
+                "{} {}",
+                name,
+                fields
+                    .iter()
+                    .map(|(field, value)| format!("{}: {}", field, value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Function(function) => write!(f, "função usando {}", function.args.join(", ")),
+            Value::Tuple(values) => write!(
+                f,
+                "{}",
+                values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" e ")
+            ),
+            Value::Void => write!(f, "nada"),
             Value::Bool(boolean) => write!(f, "{}", boolean),
             Value::String(string) => write!(f, "{}", string),
             Value::Integer(integer) => write!(f, "{}", integer),
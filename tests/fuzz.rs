@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod fuzz {
+    use std::{
+        env, fs,
+        path::Path,
+        process::{Command, Stdio},
+    };
+
+    const CASES: usize = 400;
+
+    const TOKENS: [&str; 29] = [
+        "\n",
+        " ",
+        "se ",
+        "até aqui\n",
+        "senão\n",
+        "enquanto ",
+        "defina a função ",
+        "função ",
+        " usando ",
+        " e ",
+        "retorne ",
+        "mostre ",
+        " é ",
+        " mais ",
+        " menos ",
+        " vezes ",
+        " dividido por ",
+        " dividido por 0",
+        "\"",
+        "[",
+        "]",
+        "0",
+        "-1",
+        "2147483647",
+        "-2147483648",
+        "verdadeiro",
+        "nada",
+        "tente\n",
+        "escolha ",
+    ];
+
+    /// Programs added to the corpus for the edge cases of integer arithmetic
+    const SEEDS: [&str; 8] = [
+        "mostre 1 dividido por 0\n",
+        "a é 0\nmostre 7 dividido por a\n",
+        "mostre 2147483647 mais 1\n",
+        "mostre -2147483648 menos 1\n",
+        "mostre 65536 vezes 65536\n",
+        "mostre -2147483648 dividido por -1\n",
+        "mostre valor absoluto de -2147483648\n",
+        "mostre soma de lista com 2147483647, 1\n",
+    ];
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self, max: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % max.max(1) as u64) as usize
+        }
+    }
+
+    fn corpus() -> Vec<Vec<char>> {
+        let mut programs: Vec<Vec<char>> =
+            SEEDS.iter().map(|seed| seed.chars().collect()).collect();
+        for dir in ["tests", "tests/errors"] {
+            let mut files: Vec<_> = fs::read_dir(dir)
+                .expect("Failed to read corpus")
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "ptbr"))
+                .collect();
+            files.sort();
+            for file in files {
+                let program = fs::read_to_string(file).expect("Failed to read program");
+                programs.push(program.chars().collect());
+            }
+        }
+        programs
+    }
+
+    fn mutate(program: &mut Vec<char>, rng: &mut XorShift) {
+        let start = rng.next(program.len());
+        let end = (start + rng.next(40)).min(program.len());
+        match rng.next(5) {
+            0 => {
+                program.drain(start..end);
+            }
+            1 => {
+                let copy: Vec<char> = program[start..end].to_vec();
+                let at = rng.next(program.len());
+                program.splice(at..at, copy);
+            }
+            2 => {
+                let token = TOKENS[rng.next(TOKENS.len())];
+                program.splice(start..start, token.chars());
+            }
+            3 => program.truncate(start),
+            _ => {
+                let token = TOKENS[rng.next(TOKENS.len())];
+                program.splice(start..end, token.chars());
+            }
+        }
+    }
+
+    fn run(program: &str, file: &Path) {
+        fs::write(file, program).expect("Failed to write program");
+        let output = Command::new("target/release/ptbri")
+            .args(["--passos", "20000", "--tempo", "2"])
+            .arg(file)
+            .stdin(Stdio::null())
+            .output()
+            .expect("Failed to run ptbri");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            matches!(output.status.code(), Some(0) | Some(1)) && !stderr.contains("panicked"),
+            "ptbri crashed with {} on:\n{}\n{}",
+            output.status,
+            program,
+            stderr
+        );
+    }
+
+    #[test]
+    fn no_input_panics() {
+        let build = Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .expect("Failed to run cargo");
+        assert!(build.status.success(), "Cargo build failed");
+        let corpus = corpus();
+        let file = env::temp_dir().join(format!("ptbri-fuzz-{}.ptbr", std::process::id()));
+        let mut rng = XorShift(0x5eed_1234_abcd_ef01);
+        for seed in SEEDS {
+            run(seed, &file);
+        }
+        for _ in 0..CASES {
+            let mut program = corpus[rng.next(corpus.len())].clone();
+            for _ in 0..=rng.next(4) {
+                mutate(&mut program, &mut rng);
+            }
+            run(&program.into_iter().collect::<String>(), &file);
+        }
+        for _ in 0..CASES / 4 {
+            let program: String = (0..rng.next(30))
+                .map(|_| TOKENS[rng.next(TOKENS.len())])
+                .collect();
+            run(&program, &file);
+        }
+        let _ = fs::remove_file(file);
+    }
+}
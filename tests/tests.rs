@@ -1,10 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use std::process::Command;
+    use std::{
+        env, fs,
+        io::Write,
+        process::{Command, Stdio},
+    };
 
     #[test]
     fn basic() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/basic.ptbr")
                 .output()
@@ -17,7 +25,11 @@ mod tests {
 
     #[test]
     fn if_statement() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/if_statement.ptbr")
                 .output()
@@ -30,7 +42,11 @@ mod tests {
 
     #[test]
     fn senao() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/senao.ptbr")
                 .output()
@@ -43,7 +59,11 @@ mod tests {
 
     #[test]
     fn while_statement() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/while_statement.ptbr")
                 .output()
@@ -54,9 +74,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn retorne_enquanto() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/retorne_enquanto.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "21\nfim\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
     #[test]
     fn math() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/math.ptbr")
                 .output()
@@ -69,7 +110,11 @@ mod tests {
 
     #[test]
     fn boolean() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/boolean.ptbr")
                 .output()
@@ -85,7 +130,11 @@ mod tests {
 
     #[test]
     fn function() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/function.ptbr")
                 .output()
@@ -98,7 +147,11 @@ mod tests {
 
     #[test]
     fn function_return() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/function_return.ptbr")
                 .output()
@@ -111,7 +164,11 @@ mod tests {
 
     #[test]
     fn fibonacci_iterative() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/fibonacci_iterative.ptbr")
                 .output()
@@ -124,7 +181,11 @@ mod tests {
 
     #[test]
     fn function_recursion() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/function_recursion.ptbr")
                 .output()
@@ -137,7 +198,11 @@ mod tests {
 
     #[test]
     fn hanoi_towers_recursion() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/hanoi_towers_recursion.ptbr")
                 .output()
@@ -150,7 +215,11 @@ mod tests {
 
     #[test]
     fn ackermann() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/ackermann.ptbr")
                 .output()
@@ -163,7 +232,11 @@ mod tests {
 
     #[test]
     fn comments() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/comments.ptbr")
                 .output()
@@ -176,7 +249,11 @@ mod tests {
 
     #[test]
     fn nested_function() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/nested_function.ptbr")
                 .output()
@@ -189,7 +266,11 @@ mod tests {
 
     #[test]
     fn and_or() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
                 .arg("tests/and_or.ptbr")
                 .output()
@@ -199,21 +280,263 @@ mod tests {
             panic!("Cargo build failed");
         }
     }
-}
+    #[test]
+    fn math_functions() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/math_functions.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "4\n1024\n1.4142135\n3.14\n3\n2 3\n4 4.5\n0 1\n3.1415927\n2.5 20\n5\n19\nok\n16777217 16777217\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn random() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--semente", "7", "tests/random.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "88 maçã\n4 2\n4 2\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn conversion() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/conversion.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "43 inteiro\n3.5 real\n3 7\n15! texto\nlógico lista\nok\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn entrada_numero() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/entrada_numero.ptbr")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("5\n2.5\n41\n7\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "a é 5\n2.5 real\n42 inteiro\naprovado\nmenor\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn entrada_mensagem() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/entrada_mensagem.ptbr")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("30\nAna\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "Idade: Nome: Ana tem 30 anos\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn entrada_sim_lista() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/entrada_sim_lista.ptbr")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("S\n7; 8.5 10\nnão\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Continuar? Notas: notas: 7, 8.5, 10\nfalse\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn entrada_lista_decimais() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/entrada_sim_lista.ptbr")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("s\n8,5;7 9,25\nn\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Continuar? Notas: notas: 8.5, 7, 9.25\nfalse\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn dictionary() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/dictionary.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Maria: 10, João: 8\n10\nJoão tirou 9\nAna tirou 9.5\nMaria saiu\ndicionário\nmaçã\nuva\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn record() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/record.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Aluno nome: Ana, idade: 20, nota: 9.5\nAna tem 20 anos\n10\nAluno\nAna\nmaior de idade\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+    #[test]
+    fn function_value() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/function_value.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "42\n10\n15\n3\nfunção\n10\n3\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
 
-mod errors {
-    use std::process::Command;
+    #[test]
+    fn list_operations() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/list_operations.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "14, 8, 18, 10\n7, 9\n25\n1260\n4, 5, 7, 9\n9, 7, 5, 4\nbanana, pera, uva\ntrue\nfalse\n25\n6.25\n6\n9\n4\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
 
     #[test]
-    fn undefined_var() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn tente() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/undefined_var.ptbr")
+                .arg("tests/tente.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
-                output.stderr,
-                "Error: Variable \"a\" not defined\n".as_bytes()
+                output.stdout,
+                "A chave \"bia\" não existe no dicionário\nexecução\nErro mensagem: Não é possível dividir por zero, categoria: usuário\n5\nNão é possível fazer adição entre os tipos qualquer e lista\n"
+                    .as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -221,15 +544,20 @@ mod errors {
     }
 
     #[test]
-    fn undefined_function() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn garanta() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/undefined_function.ptbr")
+                .arg("tests/garanta.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
-                output.stderr,
-                "Error: Function \"teste\" not defined\n".as_bytes()
+                output.stdout,
+                "A verificação \"nota for maior que 8\" falhou com nota = 7: A nota deveria passar de 8\n"
+                    .as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -237,15 +565,19 @@ mod errors {
     }
 
     #[test]
-    fn not_enough_args() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn escolha() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/not_enough_args.ptbr")
+                .arg("tests/escolha.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
-                output.stderr,
-                "Error: Function teste expected 2 arguments but 1 was supplied\n".as_bytes()
+                output.stdout,
+                "perfeita\nboa\nregular\nbaixa\nboa\n0\n".as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -253,15 +585,20 @@ mod errors {
     }
 
     #[test]
-    fn illegal_operation() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn escolha_repetido() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/illegal_operation.ptbr")
+                .arg("tests/escolha_repetido.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "um\n".as_bytes());
             assert_eq!(
                 output.stderr,
-                "Error: Cannot perform addition, between types any and bool\n".as_bytes()
+                "Warning: case 1 is repeated in escolha 1\n".as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -269,15 +606,23 @@ mod errors {
     }
 
     #[test]
-    fn pest_parse_error() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn constante() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/pest_parse_error.ptbr")
+                .arg("tests/constante.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
-                output.stderr,
-                "Error: Parsing failed, reason:\n --> 1:1\n  |\n1 | mostr a mais a\n  | ^---\n  |\n  = expected EOI or line\n".as_bytes()
+                output.stdout,
+                "6.28318\nNão é possível alterar a constante \"LIMITE\"\n10\n\
+                 Não é possível alterar a constante \"NOTAS\"\n\
+                 Não é possível alterar a constante \"NOTAS\"\nAna: 9\n\
+                 Não é possível alterar a constante \"ORIGEM\"\n0\n"
+                    .as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -285,15 +630,22 @@ mod errors {
     }
 
     #[test]
-    fn incomplete_expression() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn constantes_maiusculas() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/incomplete_expression.ptbr")
+                .args([
+                    "--constantes-maiusculas",
+                    "tests/constantes_maiusculas.ptbr",
+                ])
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
-                output.stderr,
-                "Error: Parsing failed, reason:\n --> 1:15\n  |\n1 | mostre 1 mais \n  |               ^---\n  |\n  = expected ident, function_call, verdadeiro, falso, integer, float, or string\n".as_bytes()
+                output.stdout,
+                "Não é possível alterar a constante \"TAXA\"\n5 2\n".as_bytes()
             )
         } else {
             panic!("Cargo build failed");
@@ -301,15 +653,1146 @@ mod errors {
     }
 
     #[test]
-    fn infinite_recursion() {
-        if let Ok(_) = Command::new("cargo").args(&["build", "--release"]).output() {
+    fn tipos() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
             let output = Command::new("target/release/ptbri")
-                .arg("tests/errors/infinite_recursion.ptbr")
+                .arg("tests/tipos.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "15\nOlá Ana\n11\nA variável \"valor\" foi declarada como número mas recebeu texto\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn importe() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/importe.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "12.56\n9\nPonto x: 1, y: 2\n3.14\nNão é possível alterar a constante \"PI\"\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn hoisting() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/hoisting.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "8\n6\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn argumentos() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/argumentos.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Olá Ana\nOi Bia\nOlá Caio\nBom dia Davi\n27\n16\nA função saudação não tem o argumento \"apelido\"\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn multiplos_retornos() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/multiplos_retornos.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "3\n1\n3 e 2\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn recursao_de_cauda() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/recursao_de_cauda.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "100000\n3000\nfalhou\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn condicoes_flexiveis() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--condicoes-flexiveis", "tests/condicoes_flexiveis.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "vazia\ncom texto\n3\n2\n1\npronto\n1, 3\ntrue\nfalse\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn nada() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/nada.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "a função não retornou nada\nnada\nv é nada\n5\nsem total\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn closure_functions() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/closure_functions.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "11\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn mensagens() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/mensagens.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Não é possível calcular raiz quadrada de -1\n\
+                 Não é possível tratar inteiro como lógico na condição \"1 mais 1\"\n\
+                 Não é possível fazer adição entre os tipos qualquer e lógico\n\
+                 Não é possível calcular soma do tipo inteiro, era esperada uma lista\n\
+                 Não é possível calcular média de uma lista vazia\n\
+                 Não é possível fazer mapeamento com o tipo inteiro, era esperada uma função\n\
+                 Não é possível fazer busca de chave com o tipo inteiro, era esperado um dicionário\n\
+                 Não é possível fazer multiplicação com nada\n\
+                 Não é possível fazer adição com nada, a função vazia não retornou nenhum valor\n\
+                 Não é possível fazer multiplicação com nada, talvez uma função usada nela não tenha retornado nenhum valor\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn nested_blocks() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let program = format!(
+                "{}mostre \"fundo\"\n{}",
+                "se 1 é 1\n".repeat(1000),
+                "até aqui\n".repeat(1000)
+            );
+            let file = env::temp_dir().join(format!("ptbri-nested-{}.ptbr", std::process::id()));
+            fs::write(&file, program).expect("Failed to write program");
+            let output = Command::new("target/release/ptbri")
+                .arg(&file)
+                .output()
+                .expect("Failed to run ptbri");
+            let _ = fs::remove_file(file);
+            assert_eq!(output.stdout, "fundo\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn calculator() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/calculator.ptbr")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("+\n2\n3\n%\n1\n1\n*\n4\nabc\n*\n4\n2.5\n/\n7\n0\nsair\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "Bem vindo à calculadora escrita em PTBR!\nDigite a operação (+, -, *, /, sair):\nDigite o valor da esquerda:\nDigite o valor da direita:\nResultado: 5\nDigite a operação (+, -, *, /, sair):\nDigite o valor da esquerda:\nDigite o valor da direita:\nErro: Operação desconhecida\nDigite a operação (+, -, *, /, sair):\nDigite o valor da esquerda:\nDigite o valor da direita:\nErro: Não foi possível ler abc como número\nDigite a operação (+, -, *, /, sair):\nDigite o valor da esquerda:\nDigite o valor da direita:\nResultado: 10\nDigite a operação (+, -, *, /, sair):\nDigite o valor da esquerda:\nDigite o valor da direita:\nErro: Não é possível dividir um inteiro por zero\nDigite a operação (+, -, *, /, sair):\n".as_bytes())
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+}
+
+mod errors {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    #[test]
+    fn undefined_var() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/undefined_var.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Variable \"a\" not defined\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn undefined_function() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/undefined_function.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Function \"teste\" not defined\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn not_enough_args() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/not_enough_args.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Function teste is missing the argument \"b\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn illegal_operation() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/illegal_operation.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform addition, between types any and bool\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn pest_parse_error() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/pest_parse_error.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Parsing failed, reason:\n --> 1:1\n  |\n1 | mostr a mais a\n  | ^---\n  |\n  = expected EOI or line\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn incomplete_expression() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/incomplete_expression.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Parsing failed, reason:\n --> 1:15\n  |\n1 | mostre 1 mais \n  |               ^---\n  |\n  = expected ident, math_call, anonymous_function, function_call, list, dictionary, new_record, list_call, random_number, random_item, conversion, type_of, verdadeiro, falso, nada, integer, float, or string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn infinite_recursion() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/infinite_recursion.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            assert!(stderr.starts_with("Error: Reached memory limit of 64 MiB\n"));
+            assert!(stderr.ends_with("vezes)\nem função infinity (linha 5)\n"));
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn not_a_number() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/not_a_number.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform square root on type string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn empty_list() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/empty_list.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot take random item of an empty list\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn conversion_error() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/conversion_error.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stdout,
+                "Não foi possível ler 99999999999 como inteiro\n\
+                 Não foi possível ler 5000000000 como inteiro\n"
+                    .as_bytes()
+            );
+            assert_eq!(
+                output.stderr,
+                "Error: Couldn't parse doze as a number\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn entrada_inteiro() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/errors/entrada_inteiro.ptbr")
+                .stdin(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("2.5\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Couldn't parse 2.5 as a integer\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn end_of_input() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/errors/end_of_input.ptbr")
+                .stdin(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("3\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Reached the end of the input\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn entrada_sim_nao() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let mut child = Command::new("target/release/ptbri")
+                .arg("tests/errors/entrada_sim_nao.ptbr")
+                .stdin(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to run ptbri");
+            child
+                .stdin
+                .take()
+                .expect("Failed to open stdin")
+                .write_all("talvez\n".as_bytes())
+                .expect("Failed to write to stdin");
+            let output = child.wait_with_output().expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Couldn't parse talvez as a yes or no answer\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn missing_key() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/missing_key.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Key \"Pedro\" not found in dictionary\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn unknown_field() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/unknown_field.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Record Aluno has no field \"nota\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn not_a_function() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/not_a_function.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform mapping with type integer, expected a function\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn assertion_failed() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/assertion_failed.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Assertion \"nome é \"Ana\" e nota for maior que 5\" failed with nome = \"Ana\", nota = 4: Nota abaixo da média\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn constant_reassignment() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/constant_reassignment.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot reassign constant \"PI\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn constant_key() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/constant_key.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot reassign constant \"NOTAS\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn list_trace() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/list_trace.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Variable \"c\" not defined\n\
+                 em função conte (linha 6) usando 0 (depois de 3 chamadas de cauda)\n\
+                 em função mapear usando 3\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn division_by_zero() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/division_by_zero.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot divide an integer by zero\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn integer_overflow() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/integer_overflow.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Result of addition is too large for an integer\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn variable_type() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/variable_type.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "".as_bytes());
+            assert_eq!(
+                output.stderr,
+                "Error: Variable \"idade\" was declared as number but got string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn argument_type() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/argument_type.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "".as_bytes());
+            assert_eq!(
+                output.stderr,
+                "Error: Argument \"a\" of function soma expects number but got string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn return_type() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/return_type.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "".as_bytes());
+            assert_eq!(
+                output.stderr,
+                "Error: Function dobro should return number but returns string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn static_operation() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/static_operation.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "".as_bytes());
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform multiplication, between types any and string\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn cyclic_import() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/cyclic_import.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: In imported file \"modulos/ciclo.ptbr\": Cyclic import of \"../cyclic_import.ptbr\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn imported_error() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/imported_error.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: In imported file \"modulos/com_erro.ptbr\": Function dobro expected 1 arguments but 2 were supplied\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn missing_arguments() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/missing_arguments.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Function volume is missing the arguments \"largura\" and \"altura\"\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn wrong_number_of_values() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/wrong_number_of_values.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Expected 3 values but got 2\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn step_limit() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--passos", "1000", "tests/errors/infinite_loop.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Reached step limit of 1000\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn time_limit() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--tempo", "0.2", "tests/errors/infinite_loop.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Reached time limit of 0.2 seconds\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn memory_limit() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--memoria", "1", "tests/errors/infinite_recursion.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.status.code(), Some(1));
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            assert!(stderr.starts_with("Error: Reached memory limit of 1 MiB\n"));
+            assert!(stderr.ends_with("vezes)\nem função infinity (linha 5)\n"));
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn depth_limit() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args([
+                    "--profundidade",
+                    "100",
+                    "tests/errors/infinite_recursion.ptbr",
+                ])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Reached recursion limit of 100\nem função infinity (linha 2) (99 vezes)\nem função infinity (linha 5)\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn caught_depth_limit() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .args(["--profundidade", "50", "tests/errors/caught_recursion.ptbr"])
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(output.stdout, "".as_bytes());
+            assert_eq!(
+                output.stderr,
+                "Error: Reached recursion limit of 50\nem função infinity (linha 2) (49 vezes)\nem função infinity (linha 6)\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn stack_trace() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/stack_trace.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Variable \"c\" not defined\nem função divida (linha 7) usando 8, 2\nem função média (linha 11) usando 3, 5\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn argument_error() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/argument_error.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Variable \"x\" not defined\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn condition_not_bool() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/condition_not_bool.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot treat Integer as a Bool type in condition \"n mais 1\"\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn void_arithmetic() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/void_arithmetic.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform addition with nada, a function used in it may have returned nothing\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn void_function_arithmetic() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/void_function_arithmetic.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Cannot perform addition with nada, function esquecida returned nothing\n"
+                    .as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn absolute_overflow() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/absolute_overflow.ptbr")
+                .output()
+                .expect("Failed to run ptbri");
+            assert_eq!(
+                output.stderr,
+                "Error: Result of absolute value is too large for an integer\n".as_bytes()
+            )
+        } else {
+            panic!("Cargo build failed");
+        }
+    }
+
+    #[test]
+    fn floor_overflow() {
+        if Command::new("cargo")
+            .args(["build", "--release"])
+            .output()
+            .is_ok()
+        {
+            let output = Command::new("target/release/ptbri")
+                .arg("tests/errors/floor_overflow.ptbr")
                 .output()
                 .expect("Failed to run ptbri");
             assert_eq!(
                 output.stderr,
-                "Error: Reached recursion limit of 4000\n".as_bytes()
+                "Error: Result of ceiling is too large for an integer\n".as_bytes()
             )
         } else {
             panic!("Cargo build failed");